@@ -0,0 +1,114 @@
+//! Known correct answers for the real puzzle input, stored in an `answers` file next to the
+//! `dayNN` input files. Each line holds the day, the part and the answer separated by spaces.
+//! Picture answers are written as one line per row with the row prefixed by `|`. Empty lines and
+//! lines starting with `#` are ignored.
+//!
+//! ```text
+//! # day part answer
+//! 1 1 72017
+//! 10 2 |███   ██  █  █
+//! 10 2 |█  █ █  █ █  █
+//! ```
+use crate::input;
+use crate::solutions::{Answer, Part};
+use std::collections::HashMap;
+use std::path::Path;
+
+pub const FILE_NAME: &str = "answers";
+
+#[derive(Debug)]
+pub enum Error {
+    Read(input::Error),
+    Malformed { line: usize, content: String },
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Error::Read(err) => write!(f, "{}", err),
+            Error::Malformed { line, content } => {
+                write!(f, "malformed answer on line {}: `{}`", line, content)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Answers {
+    inner: HashMap<(i32, Part), Answer>,
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        Self::parse(&input::raw_file(path).map_err(Error::Read)?)
+    }
+
+    pub fn parse(input: &str) -> Result<Self, Error> {
+        let mut answers = Self::default();
+
+        for (i, line) in input.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let malformed = || Error::Malformed {
+                line: i + 1,
+                content: line.to_string(),
+            };
+
+            let mut it = line.splitn(3, ' ');
+            let (Some(day), Some(part), Some(value)) = (it.next(), it.next(), it.next()) else {
+                return Err(malformed());
+            };
+
+            let day = day.parse::<i32>().map_err(|_| malformed())?;
+            let part = match part {
+                "1" => Part::One,
+                "2" => Part::Two,
+                _ => return Err(malformed()),
+            };
+
+            match (value.strip_prefix('|'), answers.inner.get_mut(&(day, part))) {
+                (Some(row), Some(Answer::Picture(rows))) => rows.push(row.to_string()),
+                (Some(row), None) => {
+                    answers
+                        .inner
+                        .insert((day, part), Answer::Picture(vec![row.to_string()]));
+                }
+                (None, None) => {
+                    answers.inner.insert((day, part), value.parse().unwrap());
+                }
+                (_, Some(_)) => return Err(malformed()),
+            }
+        }
+
+        Ok(answers)
+    }
+
+    pub fn get(&self, day: i32, part: Part) -> Option<&Answer> {
+        self.inner.get(&(day, part))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let answers =
+            Answers::parse("# day part answer\n1 1 72017\n5 2 RGLVRCQSB\n\n10 2 |█ █\n10 2 | █ \n")
+                .unwrap();
+
+        assert_eq!(answers.get(1, Part::One), Some(&Answer::Integer(72017)));
+        assert_eq!(answers.get(1, Part::Two), None);
+        assert_eq!(answers.get(5, Part::Two), Some(&Answer::from("RGLVRCQSB")));
+        assert_eq!(
+            answers.get(10, Part::Two),
+            Some(&Answer::Picture(vec!["█ █".to_string(), " █ ".to_string()]))
+        );
+
+        assert!(Answers::parse("1 3 5").is_err());
+        assert!(Answers::parse("1 1 5\n1 1 6").is_err());
+    }
+}
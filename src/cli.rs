@@ -0,0 +1,310 @@
+use crate::input::Source;
+use crate::solutions::Part;
+use std::path::PathBuf;
+
+pub const USAGE: &str = "Usage: advent-of-code-2022 [COMMAND] [OPTIONS]
+
+Commands:
+  run <DAYS>             Run a single day (e.g. 5), an inclusive range (e.g. 1-10) or `all`
+  all                    Run all implemented days
+  bench <DAYS>           Benchmark parsing and each part of a single day, a range or `all`
+  verify <DAYS>          Check answers against the `answers` file in the input directory
+  help                   Show this message
+  <DAY>                  Shorthand for `run <DAY>`
+
+Options:
+  -p, --part <1|2>       Only run the given part
+  -i, --input <PATH>     Read the puzzle input from PATH, or stdin if PATH is `-`. Only valid
+                         for a single day
+  -d, --input-dir <DIR>  Read input for each day from DIR/dayNN. Defaults to $AOC_INPUT_DIR or
+                         `input` in the crate root
+  -t, --time             Show how long parsing the input and each part took
+  -f, --format <FORMAT>  Output format, `text` or `json` (default `text`)
+  -r, --runs <N>         Number of measured runs when benchmarking (default 10)
+  -w, --warmup <N>       Number of unmeasured runs before benchmarking (default 2)";
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run(Options),
+    Help,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Days {
+    Single(i32),
+    Range(i32, i32),
+    All,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bench {
+    pub runs: usize,
+    pub warmup: usize,
+}
+
+impl Default for Bench {
+    fn default() -> Self {
+        Self {
+            runs: 10,
+            warmup: 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mode {
+    Run,
+    Bench(Bench),
+    Verify,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Options {
+    pub days: Days,
+    pub part: Option<Part>,
+    pub input: Source,
+    pub time: bool,
+    pub format: Format,
+    pub mode: Mode,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    MissingCommand,
+    MissingValue(String),
+    UnknownArgument(String),
+    InvalidDay(String),
+    InvalidPart(String),
+    InvalidFormat(String),
+    InvalidNumber(String),
+    InputWithMultipleDays,
+    ConflictingInput,
+    BenchOptionWithoutBench,
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Error::MissingCommand => write!(f, "missing command"),
+            Error::MissingValue(flag) => write!(f, "missing value for `{}`", flag),
+            Error::UnknownArgument(arg) => write!(f, "unknown argument `{}`", arg),
+            Error::InvalidDay(day) => write!(f, "invalid day `{}`, expected 1-25", day),
+            Error::InvalidPart(part) => write!(f, "invalid part `{}`, expected 1 or 2", part),
+            Error::InvalidFormat(format) => {
+                write!(f, "invalid format `{}`, expected text or json", format)
+            }
+            Error::InvalidNumber(n) => write!(f, "invalid number `{}`", n),
+            Error::InputWithMultipleDays => write!(f, "`--input` can only be used with one day"),
+            Error::ConflictingInput => {
+                write!(f, "only one of `--input` and `--input-dir` can be used")
+            }
+            Error::BenchOptionWithoutBench => {
+                write!(f, "`--runs` and `--warmup` can only be used with `bench`")
+            }
+        }
+    }
+}
+
+pub fn parse<I>(args: I) -> Result<Command, Error>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter();
+    let mut days = None;
+    let mut options = Options {
+        days: Days::All,
+        part: None,
+        input: Source::default(),
+        time: false,
+        format: Format::Text,
+        mode: Mode::Run,
+    };
+    let (mut runs, mut warmup) = (None, None);
+    let mut input = None;
+
+    while let Some(arg) = args.next() {
+        let mut value_for = |flag: &str| args.next().ok_or(Error::MissingValue(flag.to_string()));
+
+        match arg.as_str() {
+            "-h" | "--help" | "help" => return Ok(Command::Help),
+            "-p" | "--part" => options.part = Some(parse_part(&value_for(&arg)?)?),
+            "-i" | "--input" if input.is_none() => {
+                input = Some(match value_for(&arg)?.as_str() {
+                    "-" => Source::Stdin,
+                    path => Source::File(PathBuf::from(path)),
+                })
+            }
+            "-d" | "--input-dir" if input.is_none() => {
+                input = Some(Source::Directory(PathBuf::from(value_for(&arg)?)))
+            }
+            "-i" | "--input" | "-d" | "--input-dir" => return Err(Error::ConflictingInput),
+            "-t" | "--time" => options.time = true,
+            "-f" | "--format" => options.format = parse_format(&value_for(&arg)?)?,
+            "-r" | "--runs" => runs = Some(parse_number(&value_for(&arg)?)?),
+            "-w" | "--warmup" => warmup = Some(parse_number(&value_for(&arg)?)?),
+            "run" if days.is_none() => days = Some(parse_days(&value_for(&arg)?)?),
+            "bench" if days.is_none() => {
+                days = Some(parse_days(&value_for(&arg)?)?);
+                options.mode = Mode::Bench(Bench::default());
+            }
+            "verify" if days.is_none() => {
+                days = Some(parse_days(&value_for(&arg)?)?);
+                options.mode = Mode::Verify;
+            }
+            "all" if days.is_none() => days = Some(Days::All),
+            a if days.is_none() && !a.starts_with('-') => days = Some(parse_days(a)?),
+            a => return Err(Error::UnknownArgument(a.to_string())),
+        }
+    }
+
+    options.days = days.ok_or(Error::MissingCommand)?;
+
+    match &mut options.mode {
+        Mode::Bench(bench) => {
+            bench.runs = runs.unwrap_or(bench.runs);
+            bench.warmup = warmup.unwrap_or(bench.warmup);
+        }
+        _ if runs.is_some() || warmup.is_some() => return Err(Error::BenchOptionWithoutBench),
+        _ => (),
+    }
+
+    if let Some(input) = input {
+        options.input = input;
+    }
+
+    if !matches!(options.input, Source::Directory(_)) && !matches!(options.days, Days::Single(_)) {
+        return Err(Error::InputWithMultipleDays);
+    }
+
+    Ok(Command::Run(options))
+}
+
+fn parse_day(day: &str) -> Result<i32, Error> {
+    match day.parse::<i32>() {
+        Ok(d) if (1..=25).contains(&d) => Ok(d),
+        _ => Err(Error::InvalidDay(day.to_string())),
+    }
+}
+
+fn parse_days(days: &str) -> Result<Days, Error> {
+    if days == "all" {
+        return Ok(Days::All);
+    }
+
+    match days.split_once('-') {
+        Some((from, to)) => {
+            let (from, to) = (parse_day(from)?, parse_day(to)?);
+            if from > to {
+                return Err(Error::InvalidDay(days.to_string()));
+            }
+
+            Ok(Days::Range(from, to))
+        }
+        None => Ok(Days::Single(parse_day(days)?)),
+    }
+}
+
+fn parse_part(part: &str) -> Result<Part, Error> {
+    match part {
+        "1" => Ok(Part::One),
+        "2" => Ok(Part::Two),
+        _ => Err(Error::InvalidPart(part.to_string())),
+    }
+}
+
+fn parse_number(n: &str) -> Result<usize, Error> {
+    n.parse().map_err(|_| Error::InvalidNumber(n.to_string()))
+}
+
+fn parse_format(format: &str) -> Result<Format, Error> {
+    match format {
+        "text" => Ok(Format::Text),
+        "json" => Ok(Format::Json),
+        _ => Err(Error::InvalidFormat(format.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_days() {
+        let days = |s: &str| match parse(args(s)) {
+            Ok(Command::Run(options)) => Ok(options.days),
+            Ok(Command::Help) => unreachable!(),
+            Err(err) => Err(err),
+        };
+
+        assert_eq!(days("5"), Ok(Days::Single(5)));
+        assert_eq!(days("run 5"), Ok(Days::Single(5)));
+        assert_eq!(days("run 1-10"), Ok(Days::Range(1, 10)));
+        assert_eq!(days("all"), Ok(Days::All));
+        assert_eq!(days("run all"), Ok(Days::All));
+        assert_eq!(days("verify 1-3"), Ok(Days::Range(1, 3)));
+        assert_eq!(days(""), Err(Error::MissingCommand));
+        assert_eq!(days("26"), Err(Error::InvalidDay("26".to_string())));
+        assert_eq!(days("10-1"), Err(Error::InvalidDay("10-1".to_string())));
+    }
+
+    #[test]
+    fn parse_options() {
+        assert_eq!(
+            parse(args("run 3 --part 2 -i foo -t --format json")),
+            Ok(Command::Run(Options {
+                days: Days::Single(3),
+                part: Some(Part::Two),
+                input: Source::File(PathBuf::from("foo")),
+                time: true,
+                format: Format::Json,
+                mode: Mode::Run,
+            }))
+        );
+
+        assert_eq!(
+            parse(args("bench all -r 5")),
+            Ok(Command::Run(Options {
+                days: Days::All,
+                part: None,
+                input: Source::default(),
+                time: false,
+                format: Format::Text,
+                mode: Mode::Bench(Bench { runs: 5, warmup: 2 }),
+            }))
+        );
+
+        let input = |s: &str| match parse(args(s)) {
+            Ok(Command::Run(options)) => options.input,
+            _ => unreachable!(),
+        };
+
+        assert_eq!(input("3 -i -"), Source::Stdin);
+        assert_eq!(input("all -d foo"), Source::Directory(PathBuf::from("foo")));
+
+        assert_eq!(
+            parse(args("3 --part 3")),
+            Err(Error::InvalidPart("3".to_string()))
+        );
+        assert_eq!(parse(args("all -i foo")), Err(Error::InputWithMultipleDays));
+        assert_eq!(parse(args("3 -r 5")), Err(Error::BenchOptionWithoutBench));
+        assert_eq!(parse(args("3 -i a -d b")), Err(Error::ConflictingInput));
+        assert_eq!(
+            parse(args("3 --bogus")),
+            Err(Error::UnknownArgument("--bogus".to_string()))
+        );
+        assert_eq!(
+            parse(args("3 --part")),
+            Err(Error::MissingValue("--part".to_string()))
+        );
+    }
+}
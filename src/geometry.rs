@@ -0,0 +1,206 @@
+#![allow(dead_code)]
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A point or vector in two dimensions. The y axis grows downwards so `Direction::Up` is a step
+/// towards negative y, matching how puzzle input is read line by line.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point::new(0, 0);
+
+    pub const fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+
+    pub fn manhattan(&self, other: Point) -> i32 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// The distance when diagonal steps are allowed, i.e. the number of king moves.
+    pub fn chebyshev(&self, other: Point) -> i32 {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+
+    /// A vector with each component clamped to -1, 0 or 1, useful to take a single step towards
+    /// another point.
+    pub fn signum(&self) -> Point {
+        Point::new(self.x.signum(), self.y.signum())
+    }
+
+    /// The eight points surrounding this one, starting straight up and going clockwise.
+    pub fn neighbors8(&self) -> [Point; 8] {
+        [
+            Point::new(0, -1),
+            Point::new(1, -1),
+            Point::new(1, 0),
+            Point::new(1, 1),
+            Point::new(0, 1),
+            Point::new(-1, 1),
+            Point::new(-1, 0),
+            Point::new(-1, -1),
+        ]
+        .map(|step| *self + step)
+    }
+
+    /// The four points straight up, right, down and left of this one.
+    pub fn neighbors4(&self) -> [Point; 4] {
+        Direction::ALL.map(|d| *self + d.step())
+    }
+}
+
+impl From<(i32, i32)> for Point {
+    fn from((x, y): (i32, i32)) -> Self {
+        Point::new(x, y)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Mul<i32> for Point {
+    type Output = Point;
+
+    fn mul(self, n: i32) -> Point {
+        Point::new(self.x * n, self.y * n)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, other: Point) {
+        *self = *self - other;
+    }
+}
+
+impl std::fmt::Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// All directions in clockwise order starting with `Up`.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub fn step(&self) -> Point {
+        match self {
+            Direction::Up => Point::new(0, -1),
+            Direction::Right => Point::new(1, 0),
+            Direction::Down => Point::new(0, 1),
+            Direction::Left => Point::new(-1, 0),
+        }
+    }
+
+    pub fn turn_right(&self) -> Self {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    pub fn turn_left(&self) -> Self {
+        self.opposite().turn_right()
+    }
+
+    pub fn opposite(&self) -> Self {
+        self.turn_right().turn_right()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ParseDirectionError(String);
+
+impl std::fmt::Display for ParseDirectionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "invalid direction `{}`", self.0)
+    }
+}
+
+impl std::str::FromStr for Direction {
+    type Err = ParseDirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "U" => Ok(Direction::Up),
+            "R" => Ok(Direction::Right),
+            "D" => Ok(Direction::Down),
+            "L" => Ok(Direction::Left),
+            _ => Err(ParseDirectionError(s.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn point() {
+        let (a, b) = (Point::new(1, 2), Point::new(4, -2));
+
+        assert_eq!(a + b, Point::new(5, 0));
+        assert_eq!(a - b, Point::new(-3, 4));
+        assert_eq!(a * 3, Point::new(3, 6));
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!((b - a).signum(), Point::new(1, -1));
+    }
+
+    #[test]
+    fn direction() {
+        assert_eq!("U".parse(), Ok(Direction::Up));
+        assert_eq!(
+            "X".parse::<Direction>(),
+            Err(ParseDirectionError("X".to_string()))
+        );
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.opposite(), Direction::Right);
+        assert_eq!(Direction::Down.step(), Point::new(0, 1));
+    }
+}
@@ -0,0 +1,198 @@
+#![allow(dead_code)]
+use crate::geometry::Point;
+
+/// A dense two dimensional grid stored row by row. Positions are points where `x` is the column
+/// and `y` is the row, starting at the top left corner. Points are signed so stepping outside the
+/// grid is just a lookup that returns `None`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            cells: vec![fill; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// Parse a grid from lines of text, converting each character with `f`. All lines must have
+    /// the same length.
+    pub fn from_lines<S, F>(lines: &[S], mut f: F) -> Self
+    where
+        S: AsRef<str>,
+        F: FnMut(char) -> T,
+    {
+        let height = lines.len();
+        let width = lines
+            .first()
+            .map(|l| l.as_ref().chars().count())
+            .unwrap_or(0);
+        let cells = lines
+            .iter()
+            .flat_map(|l| l.as_ref().chars())
+            .map(&mut f)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "all lines must have the same length"
+        );
+
+        Self {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, pos: Point) -> bool {
+        pos.x >= 0 && pos.y >= 0 && (pos.x as usize) < self.width && (pos.y as usize) < self.height
+    }
+
+    fn index(&self, pos: Point) -> Option<usize> {
+        self.contains(pos)
+            .then(|| pos.y as usize * self.width + pos.x as usize)
+    }
+
+    pub fn get(&self, pos: Point) -> Option<&T> {
+        self.index(pos).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, pos: Point) -> Option<&mut T> {
+        self.index(pos).map(|i| &mut self.cells[i])
+    }
+
+    /// Set the value at `pos`, returning false if it's outside the grid.
+    pub fn set(&mut self, pos: Point, value: T) -> bool {
+        match self.get_mut(pos) {
+            Some(cell) => {
+                *cell = value;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Add a row at the bottom of the grid.
+    pub fn push_row(&mut self, fill: T)
+    where
+        T: Clone,
+    {
+        self.cells.extend(std::iter::repeat_n(fill, self.width));
+        self.height += 1;
+    }
+
+    /// The positions up, right, down and left of `pos` that are inside the grid.
+    pub fn neighbors4(&self, pos: Point) -> impl Iterator<Item = Point> + '_ {
+        pos.neighbors4().into_iter().filter(|p| self.contains(*p))
+    }
+
+    /// Like `neighbors4` but including the diagonals.
+    pub fn neighbors8(&self, pos: Point) -> impl Iterator<Item = Point> + '_ {
+        pos.neighbors8().into_iter().filter(|p| self.contains(*p))
+    }
+
+    pub fn row(&self, y: usize) -> impl DoubleEndedIterator<Item = &T> {
+        self.cells[y * self.width..(y + 1) * self.width].iter()
+    }
+
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> {
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width)
+    }
+
+    /// Every position in the grid together with its value, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().enumerate().map(|(i, v)| {
+            (
+                Point::new((i % self.width) as i32, (i / self.width) as i32),
+                v,
+            )
+        })
+    }
+
+    pub fn position<P>(&self, predicate: P) -> Option<Point>
+    where
+        P: Fn(&T) -> bool,
+    {
+        self.iter().find(|(_, v)| predicate(v)).map(|(p, _)| p)
+    }
+}
+
+impl<T: std::fmt::Display> std::fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<u32> {
+        Grid::from_lines(&["123", "456"], |c| c.to_digit(10).unwrap())
+    }
+
+    #[test]
+    fn access() {
+        let mut g = grid();
+
+        assert_eq!((g.width(), g.height()), (3, 2));
+        assert_eq!(g.get(Point::new(2, 1)), Some(&6));
+        assert_eq!(g.get(Point::new(3, 0)), None);
+        assert_eq!(g.get(Point::new(0, -1)), None);
+        assert!(g.set(Point::new(1, 1), 0));
+        assert!(!g.set(Point::new(1, 2), 0));
+        assert_eq!(g.position(|v| *v == 0), Some(Point::new(1, 1)));
+
+        g.push_row(9);
+        assert_eq!(g.row(2).copied().collect::<Vec<_>>(), vec![9, 9, 9]);
+    }
+
+    #[test]
+    fn iterators() {
+        let g = grid();
+
+        assert_eq!(g.row(1).copied().collect::<Vec<_>>(), vec![4, 5, 6]);
+        assert_eq!(g.column(2).copied().collect::<Vec<_>>(), vec![3, 6]);
+        let points = |ps: &[(i32, i32)]| ps.iter().map(|&p| Point::from(p)).collect::<Vec<_>>();
+
+        assert_eq!(
+            g.neighbors4(Point::ORIGIN).collect::<Vec<_>>(),
+            points(&[(1, 0), (0, 1)])
+        );
+        assert_eq!(
+            g.neighbors8(Point::new(1, 0)).collect::<Vec<_>>(),
+            points(&[(2, 0), (2, 1), (1, 1), (0, 1), (0, 0)])
+        );
+        assert_eq!(g.to_string(), "123\n456\n");
+    }
+}
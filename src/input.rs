@@ -1,19 +1,105 @@
-#![allow(dead_code)]
-use std::fs::File;
-use std::io::{stdin, stdout, BufRead, BufReader, Write};
+use std::io::{stdin, stdout, Write};
+use std::path::{Path, PathBuf};
 
-pub fn raw_file_for_day(day: i32) -> String {
-    let input_file = format!("input/day{:02}", day);
+/// Failure to load puzzle input, keeping track of which day and path it was for.
+#[derive(Debug)]
+pub struct Error {
+    pub day: Option<i32>,
+    pub path: PathBuf,
+    pub source: std::io::Error,
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self.day {
+            Some(day) if self.source.kind() == std::io::ErrorKind::NotFound => write!(
+                f,
+                "no input for day {}, put it at {}",
+                day,
+                self.path.display()
+            ),
+            Some(day) => write!(
+                f,
+                "could not read input for day {} from {}: {}",
+                day,
+                self.path.display(),
+                self.source
+            ),
+            None => write!(
+                f,
+                "could not read input from {}: {}",
+                self.path.display(),
+                self.source
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// Environment variable that overrides where the `dayNN` input files are read from.
+pub const INPUT_DIR_ENV: &str = "AOC_INPUT_DIR";
+
+/// Where to read puzzle input from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Source {
+    /// A directory containing one `dayNN` file per day.
+    Directory(PathBuf),
+    /// A single file, used as is regardless of the day.
+    File(PathBuf),
+    Stdin,
+}
 
-    std::fs::read_to_string(input_file).unwrap()
+impl Default for Source {
+    fn default() -> Self {
+        Source::Directory(default_dir())
+    }
 }
 
-pub fn file_for_day(day: i32) -> Vec<String> {
-    let input_file = format!("input/day{:02}", day);
-    let f = File::open(input_file).unwrap();
-    let r = BufReader::new(f);
+impl Source {
+    pub fn read(&self, day: i32) -> Result<String, Error> {
+        match self {
+            Source::Directory(dir) => raw_file(&dir.join(file_name(day))).map_err(|err| Error {
+                day: Some(day),
+                ..err
+            }),
+            Source::File(path) => raw_file(path),
+            Source::Stdin => std::io::read_to_string(stdin()).map_err(|source| Error {
+                day: Some(day),
+                path: PathBuf::from("stdin"),
+                source,
+            }),
+        }
+    }
+}
+
+/// The input directory from `AOC_INPUT_DIR` if set, otherwise `input` in the crate root so the
+/// binary works no matter where it's started from.
+pub fn default_dir() -> PathBuf {
+    match std::env::var_os(INPUT_DIR_ENV) {
+        Some(dir) => PathBuf::from(dir),
+        None => Path::new(env!("CARGO_MANIFEST_DIR")).join("input"),
+    }
+}
+
+fn file_name(day: i32) -> String {
+    format!("day{:02}", day)
+}
+
+pub fn raw_file(path: &Path) -> Result<String, Error> {
+    std::fs::read_to_string(path).map_err(|source| Error {
+        day: None,
+        path: path.to_path_buf(),
+        source,
+    })
+}
 
-    r.lines().filter_map(|l| l.ok()).collect()
+pub fn lines(input: &str) -> Vec<String> {
+    input.lines().map(|l| l.to_string()).collect()
 }
 
 pub fn print_and_wait(print: String) {
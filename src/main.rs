@@ -1,15 +1,31 @@
-use std::env;
+use std::process::ExitCode;
 
+mod answers;
+mod cli;
+mod geometry;
+mod grid;
 mod input;
+mod runner;
 mod solutions;
 mod time;
 
-fn main() {
-    let day = env::args()
-        .nth(1)
-        .unwrap_or_else(|| String::from("1"))
-        .parse()
-        .unwrap_or(1);
+fn main() -> ExitCode {
+    let options = match cli::parse(std::env::args().skip(1)) {
+        Ok(cli::Command::Run(options)) => options,
+        Ok(cli::Command::Help) => {
+            println!("{}", cli::USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Err(err) => {
+            eprintln!("error: {}\n\n{}", err, cli::USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
 
-    solutions::solution_for(day)
+    if let Err(err) = runner::run(&options) {
+        eprintln!("error: {}", err);
+        return ExitCode::FAILURE;
+    }
+
+    ExitCode::SUCCESS
 }
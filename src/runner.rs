@@ -0,0 +1,412 @@
+use crate::answers::{self, Answers};
+use crate::cli::{Bench, Days, Format, Mode, Options};
+use crate::input::{self, Source};
+use crate::solutions::{self, Answer, Day, Part};
+use crate::time::{self, Stats};
+use std::time::Duration;
+
+#[derive(Debug)]
+pub enum Error {
+    NotImplemented(i32),
+    NothingImplemented(i32, i32),
+    Input(input::Error),
+    Answers(answers::Error),
+    VerifyFailed(usize),
+}
+
+impl From<input::Error> for Error {
+    fn from(err: input::Error) -> Self {
+        Error::Input(err)
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Error::NotImplemented(day) => write!(f, "day {} is not implemented", day),
+            Error::NothingImplemented(from, to) => {
+                write!(f, "no days between {} and {} are implemented", from, to)
+            }
+            Error::Input(err) => write!(f, "{}", err),
+            Error::Answers(err) => write!(f, "{}", err),
+            Error::VerifyFailed(n) => write!(f, "{} answer(s) did not match", n),
+        }
+    }
+}
+
+pub fn run(options: &Options) -> Result<(), Error> {
+    let days = select_days(&options.days)?;
+    let skip_missing = !matches!(options.days, Days::Single(_));
+    let parts = match options.part {
+        Some(part) => vec![part],
+        None => Part::BOTH.to_vec(),
+    };
+
+    match options.mode {
+        Mode::Run => (),
+        Mode::Bench(bench) => {
+            for day in &days {
+                let Some(raw) = read_input(&options.input, day, skip_missing)? else {
+                    continue;
+                };
+                benchmark(day, &raw, &parts, bench, options.format);
+            }
+
+            return Ok(());
+        }
+        Mode::Verify => return verify(&days, &parts, options),
+    }
+
+    if options.days == Days::All && options.format == Format::Text {
+        print_summary(&solve_all(&days, &parts, &options.input, skip_missing)?);
+        return Ok(());
+    }
+
+    for (i, day) in days.iter().enumerate() {
+        let Some(raw) = read_input(&options.input, day, skip_missing)? else {
+            continue;
+        };
+
+        if options.format == Format::Text {
+            if i > 0 {
+                println!();
+            }
+
+            println!("Showing solution for day {}\n", day.day);
+        }
+
+        let (parsed, elapsed) = time::this(|| day.parse(&raw));
+
+        if options.time {
+            match options.format {
+                Format::Text => println!("Parsed input ({:?})", elapsed),
+                Format::Json => print_parse_json(day.day, elapsed),
+            }
+        }
+
+        for part in &parts {
+            let (answer, elapsed) = time::this(|| parsed.solve(*part));
+            let elapsed = options.time.then_some(elapsed);
+
+            match options.format {
+                Format::Text => print_text(*part, &answer, elapsed),
+                Format::Json => print_json(day.day, *part, &answer, elapsed),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Read the input for a day. When running several days, days without an input file are skipped
+/// with a note instead of stopping the whole run.
+fn read_input(source: &Source, day: &Day, skip_missing: bool) -> Result<Option<String>, Error> {
+    match source.read(day.day) {
+        Ok(raw) => Ok(Some(raw)),
+        Err(err) if skip_missing && err.source.kind() == std::io::ErrorKind::NotFound => {
+            eprintln!("skipping day {}: {}", day.day, err);
+            Ok(None)
+        }
+        Err(err) => Err(err.into()),
+    }
+}
+
+fn select_days(days: &Days) -> Result<Vec<Day>, Error> {
+    match *days {
+        Days::Single(day) => solutions::get(day)
+            .map(|d| vec![d])
+            .ok_or(Error::NotImplemented(day)),
+        Days::Range(from, to) => {
+            let selected = solutions::registry()
+                .into_iter()
+                .filter(|d| (from..=to).contains(&d.day))
+                .collect::<Vec<_>>();
+
+            if selected.is_empty() {
+                return Err(Error::NothingImplemented(from, to));
+            }
+
+            Ok(selected)
+        }
+        Days::All => Ok(solutions::registry()),
+    }
+}
+
+/// The result of solving one part of a day.
+struct Outcome {
+    day: i32,
+    part: Part,
+    answer: Answer,
+    elapsed: Duration,
+}
+
+/// The results of solving the selected parts of one day, which share one parsed input.
+struct Solved {
+    day: i32,
+    parse_elapsed: Duration,
+    outcomes: Vec<Outcome>,
+}
+
+fn solve_all(
+    days: &[Day],
+    parts: &[Part],
+    source: &Source,
+    skip_missing: bool,
+) -> Result<Vec<Solved>, Error> {
+    let mut solved = Vec::new();
+
+    for day in days {
+        let Some(raw) = read_input(source, day, skip_missing)? else {
+            continue;
+        };
+
+        let (parsed, parse_elapsed) = time::this(|| day.parse(&raw));
+        let outcomes = parts
+            .iter()
+            .map(|part| {
+                let (answer, elapsed) = time::this(|| parsed.solve(*part));
+
+                Outcome {
+                    day: day.day,
+                    part: *part,
+                    answer,
+                    elapsed,
+                }
+            })
+            .collect();
+
+        solved.push(Solved {
+            day: day.day,
+            parse_elapsed,
+            outcomes,
+        });
+    }
+
+    Ok(solved)
+}
+
+fn print_summary(solved: &[Solved]) {
+    let outcomes = solved.iter().flat_map(|s| &s.outcomes).collect::<Vec<_>>();
+
+    // Pictures don't fit in a table cell so they're printed below the table.
+    let cell = |o: &Outcome| {
+        if o.answer.is_multiline() {
+            "(see below)".to_string()
+        } else {
+            o.answer.to_string()
+        }
+    };
+
+    let width = outcomes
+        .iter()
+        .map(|o| cell(o).chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("Answer".len());
+
+    println!(
+        "{:>3}  {:>5}  {:<width$}  {:>12}",
+        "Day", "Part", "Answer", "Time"
+    );
+    println!("{:-<3}  {:-<5}  {:-<width$}  {:->12}", "", "", "", "");
+
+    // Parsing gets a row of its own, so the total is what running everything really costs.
+    for s in solved {
+        let elapsed = format!("{:.2?}", s.parse_elapsed);
+        println!(
+            "{:>3}  {:>5}  {:<width$}  {:>12}",
+            s.day, "parse", "", elapsed
+        );
+
+        for o in &s.outcomes {
+            let elapsed = format!("{:.2?}", o.elapsed);
+            println!(
+                "{:>3}  {:>5}  {:<width$}  {:>12}",
+                o.day,
+                o.part,
+                cell(o),
+                elapsed
+            );
+        }
+    }
+
+    let total = solved
+        .iter()
+        .map(|s| s.parse_elapsed + s.outcomes.iter().map(|o| o.elapsed).sum::<Duration>())
+        .sum::<Duration>();
+    println!("{:-<3}  {:-<5}  {:-<width$}  {:->12}", "", "", "", "");
+    println!(
+        "{:<10}  {:<width$}  {:>12}",
+        "Total",
+        "",
+        format!("{:.2?}", total)
+    );
+
+    for o in outcomes.iter().filter(|o| o.answer.is_multiline()) {
+        println!("\nDay {} part {}:\n{}", o.day, o.part, o.answer);
+    }
+}
+
+/// Check each part against the recorded answers, printing the difference for any mismatch.
+fn verify(days: &[Day], parts: &[Part], options: &Options) -> Result<(), Error> {
+    let dir = match &options.input {
+        Source::Directory(dir) => dir.clone(),
+        _ => input::default_dir(),
+    };
+    let answers = Answers::load(&dir.join(answers::FILE_NAME)).map_err(Error::Answers)?;
+
+    let skip_missing = !matches!(options.days, Days::Single(_));
+    let solved = solve_all(days, parts, &options.input, skip_missing)?;
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+
+    for o in solved.iter().flat_map(|s| &s.outcomes) {
+        let expected = answers.get(o.day, o.part);
+        let status = match expected {
+            Some(e) if e.matches(&o.answer) => {
+                passed += 1;
+                "pass"
+            }
+            Some(_) => {
+                failed += 1;
+                "fail"
+            }
+            None => {
+                missing += 1;
+                "missing"
+            }
+        };
+
+        match options.format {
+            Format::Text => print_verify_text(o, status, expected),
+            Format::Json => println!(
+                r#"{{"day":{},"part":{},"status":"{}","answer":{},"expected":{}}}"#,
+                o.day,
+                o.part,
+                status,
+                o.answer.to_json(),
+                expected.map(Answer::to_json).unwrap_or("null".to_string()),
+            ),
+        }
+    }
+
+    if options.format == Format::Text {
+        println!(
+            "\n{} passed, {} failed, {} without a recorded answer",
+            passed, failed, missing
+        );
+    }
+
+    if failed > 0 {
+        return Err(Error::VerifyFailed(failed));
+    }
+
+    Ok(())
+}
+
+fn print_verify_text(o: &Outcome, status: &str, expected: Option<&Answer>) {
+    println!("day {:>2} part {}: {}", o.day, o.part, status);
+
+    let Some(expected) = expected.filter(|_| status == "fail") else {
+        return;
+    };
+
+    let (expected, got) = (expected.to_string(), o.answer.to_string());
+    let (expected, got) = (
+        expected.lines().collect::<Vec<_>>(),
+        got.lines().collect::<Vec<_>>(),
+    );
+
+    for i in 0..expected.len().max(got.len()) {
+        match (expected.get(i), got.get(i)) {
+            (Some(e), Some(g)) if e == g => println!("      {}", e),
+            (e, g) => {
+                if let Some(e) = e {
+                    println!("    - {}", e);
+                }
+
+                if let Some(g) = g {
+                    println!("    + {}", g);
+                }
+            }
+        }
+    }
+}
+
+/// Benchmark parsing and each part separately so slow parsing doesn't hide in the part timings.
+fn benchmark(day: &Day, raw: &str, parts: &[Part], bench: Bench, format: Format) {
+    let mut steps = vec![(
+        "parse".to_string(),
+        time::repeat(bench.warmup, bench.runs, || day.parse(raw)),
+    )];
+
+    let parsed = day.parse(raw);
+    for part in parts {
+        let stats = time::repeat(bench.warmup, bench.runs, || parsed.solve(*part));
+        steps.push((format!("part {}", part), stats));
+    }
+
+    for (step, stats) in steps {
+        match format {
+            Format::Text => print_bench_text(day.day, &step, &stats),
+            Format::Json => print_bench_json(day.day, &step, &stats),
+        }
+    }
+}
+
+fn print_bench_text(day: i32, step: &str, stats: &Stats) {
+    println!(
+        "day {:>2} {:<6}  runs: {:<4} min: {:>10}  median: {:>10}  mean: {:>10}  max: {:>10}",
+        day,
+        step,
+        stats.runs,
+        format!("{:.2?}", stats.min),
+        format!("{:.2?}", stats.median),
+        format!("{:.2?}", stats.mean),
+        format!("{:.2?}", stats.max),
+    );
+}
+
+fn print_bench_json(day: i32, step: &str, stats: &Stats) {
+    println!(
+        r#"{{"day":{},"step":"{}","runs":{},"min_ns":{},"median_ns":{},"mean_ns":{},"max_ns":{}}}"#,
+        day,
+        step,
+        stats.runs,
+        stats.min.as_nanos(),
+        stats.median.as_nanos(),
+        stats.mean.as_nanos(),
+        stats.max.as_nanos(),
+    );
+}
+
+fn print_text(part: Part, answer: &Answer, elapsed: Option<Duration>) {
+    let took = elapsed.map(|d| format!(" ({:?})", d)).unwrap_or_default();
+
+    if answer.is_multiline() {
+        println!("Solution part {}{}:\n{}", part, took, answer);
+    } else {
+        println!("Solution part {}{}: {}", part, took, answer);
+    }
+}
+
+fn print_parse_json(day: i32, elapsed: Duration) {
+    println!(
+        r#"{{"day":{},"step":"parse","elapsed_ns":{}}}"#,
+        day,
+        elapsed.as_nanos()
+    );
+}
+
+fn print_json(day: i32, part: Part, answer: &Answer, elapsed: Option<Duration>) {
+    let took = elapsed
+        .map(|d| format!(r#","elapsed_ns":{}"#, d.as_nanos()))
+        .unwrap_or_default();
+
+    println!(
+        r#"{{"day":{},"part":{},"answer":{}{}}}"#,
+        day,
+        part,
+        answer.to_json(),
+        took
+    );
+}
@@ -0,0 +1,151 @@
+/// The answer to a single part of a day. Parts can return anything that converts into an
+/// `Answer` so the runner can format, compare and store them the same way for every day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Integer(i64),
+    BigInteger(i128),
+    String(String),
+    /// A multi-line drawing, e.g. letters rendered by a CRT, one string per row.
+    Picture(Vec<String>),
+}
+
+impl Answer {
+    pub fn is_multiline(&self) -> bool {
+        matches!(self, Answer::Picture(_))
+    }
+
+    /// Compare two answers by value, so an integer matches a big integer with the same value.
+    /// Recorded answers that look like numbers are parsed as numbers, so a string answer that
+    /// happens to only contain digits, like a SNAFU number, is compared by how it's written.
+    pub fn matches(&self, other: &Answer) -> bool {
+        match (self, other) {
+            (Answer::Integer(a), Answer::BigInteger(b))
+            | (Answer::BigInteger(b), Answer::Integer(a)) => *a as i128 == *b,
+            (Answer::String(s), n @ (Answer::Integer(_) | Answer::BigInteger(_)))
+            | (n @ (Answer::Integer(_) | Answer::BigInteger(_)), Answer::String(s)) => {
+                *s == n.to_string()
+            }
+            (a, b) => a == b,
+        }
+    }
+
+    /// Serialize the answer as a JSON value. Numbers are kept as numbers, pictures become an
+    /// array with one string per row.
+    pub fn to_json(&self) -> String {
+        match self {
+            Answer::Integer(n) => n.to_string(),
+            Answer::BigInteger(n) => n.to_string(),
+            Answer::String(s) => json_string(s),
+            Answer::Picture(rows) => format!(
+                "[{}]",
+                rows.iter()
+                    .map(|r| json_string(r))
+                    .collect::<Vec<_>>()
+                    .join(",")
+            ),
+        }
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::from('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+
+    out.push('"');
+    out
+}
+
+impl std::fmt::Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Answer::Integer(n) => write!(f, "{}", n),
+            Answer::BigInteger(n) => write!(f, "{}", n),
+            Answer::String(s) => write!(f, "{}", s),
+            Answer::Picture(rows) => write!(f, "{}", rows.join("\n")),
+        }
+    }
+}
+
+/// Parse a single line answer, picking the smallest integer type that fits and falling back to a
+/// string.
+impl std::str::FromStr for Answer {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(n) = s.parse::<i64>() {
+            Ok(Answer::Integer(n))
+        } else if let Ok(n) = s.parse::<i128>() {
+            Ok(Answer::BigInteger(n))
+        } else {
+            Ok(Answer::String(s.to_string()))
+        }
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(n: i32) -> Self {
+        Answer::Integer(n as i64)
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(n: i64) -> Self {
+        Answer::Integer(n)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Answer::Integer(n as i64)
+    }
+}
+
+impl From<i128> for Answer {
+    fn from(n: i128) -> Self {
+        Answer::BigInteger(n)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::String(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::String(s.to_string())
+    }
+}
+
+impl From<Vec<String>> for Answer {
+    fn from(rows: Vec<String>) -> Self {
+        Answer::Picture(rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches() {
+        assert!(Answer::Integer(42).matches(&Answer::BigInteger(42)));
+        assert!(Answer::from("1201").matches(&"1201".parse().unwrap()));
+        assert!("1201"
+            .parse::<Answer>()
+            .unwrap()
+            .matches(&Answer::from("1201")));
+        assert!(!Answer::from("0120").matches(&Answer::Integer(120)));
+        assert!(!Answer::from("1201").matches(&Answer::Integer(1202)));
+    }
+}
@@ -1,13 +1,24 @@
-use crate::input;
+use super::{Answer, Solver};
 
-pub fn solve() {
-    let x = input::raw_file_for_day(1);
+pub struct Day01;
 
-    println!("Solution part 1: {}", part_one(x.clone()));
-    println!("Solution part 2: {}", part_two(x));
+impl Solver for Day01 {
+    type Input = String;
+
+    fn parse(raw: &str) -> Self::Input {
+        raw.to_string()
+    }
+
+    fn part_one(input: &Self::Input) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(input: &Self::Input) -> Answer {
+        part_two(input).into()
+    }
 }
 
-fn part_one(input: String) -> i32 {
+fn part_one(input: &str) -> i32 {
     input
         .rsplit_terminator("\n\n")
         .map(|group| {
@@ -19,7 +30,7 @@ fn part_one(input: String) -> i32 {
         .unwrap()
 }
 
-fn part_two(input: String) -> i32 {
+fn part_two(input: &str) -> i32 {
     let mut groups = input
         .rsplit_terminator("\n\n")
         .map(|group| {
@@ -60,12 +71,12 @@ mod tests {
     #[test]
     fn part_one() {
         let x = input::test_vec_raw(TEST_INPUT);
-        assert_eq!(super::part_one(x), SOLUTION_ONE);
+        assert_eq!(super::part_one(&x), SOLUTION_ONE);
     }
 
     #[test]
     fn part_two() {
         let x = input::test_vec_raw(TEST_INPUT);
-        assert_eq!(super::part_two(x), SOLUTION_TWO);
+        assert_eq!(super::part_two(&x), SOLUTION_TWO);
     }
 }
@@ -1,10 +1,22 @@
+use super::{Answer, Solver};
 use crate::input;
 
-pub fn solve() {
-    let x = input::file_for_day(2);
+pub struct Day02;
 
-    println!("Solution part 1: {}", part_one(x.clone()));
-    println!("Solution part 2: {}", part_two(x));
+impl Solver for Day02 {
+    type Input = Vec<String>;
+
+    fn parse(raw: &str) -> Self::Input {
+        input::lines(raw)
+    }
+
+    fn part_one(input: &Self::Input) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(input: &Self::Input) -> Answer {
+        part_two(input).into()
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -64,7 +76,7 @@ impl Shape {
     }
 }
 
-fn part_one(input: Vec<String>) -> i32 {
+fn part_one(input: &[String]) -> i32 {
     input
         .iter()
         .map(|row| {
@@ -78,7 +90,7 @@ fn part_one(input: Vec<String>) -> i32 {
         .sum()
 }
 
-fn part_two(input: Vec<String>) -> i32 {
+fn part_two(input: &[String]) -> i32 {
     input
         .iter()
         .map(|row| {
@@ -115,12 +127,12 @@ C Z
     #[test]
     fn part_one() {
         let x = input::test_vec(TEST_INPUT);
-        assert_eq!(super::part_one(x), SOLUTION_ONE);
+        assert_eq!(super::part_one(&x), SOLUTION_ONE);
     }
 
     #[test]
     fn part_two() {
         let x = input::test_vec(TEST_INPUT);
-        assert_eq!(super::part_two(x), SOLUTION_TWO);
+        assert_eq!(super::part_two(&x), SOLUTION_TWO);
     }
 }
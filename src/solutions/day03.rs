@@ -1,11 +1,23 @@
+use super::{Answer, Solver};
 use crate::input;
 use std::collections::HashSet;
 
-pub fn solve() {
-    let x = input::file_for_day(3);
+pub struct Day03;
 
-    println!("Solution part 1: {}", part_one(x.clone()));
-    println!("Solution part 2: {}", part_two(x));
+impl Solver for Day03 {
+    type Input = Vec<String>;
+
+    fn parse(raw: &str) -> Self::Input {
+        input::lines(raw)
+    }
+
+    fn part_one(input: &Self::Input) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(input: &Self::Input) -> Answer {
+        part_two(input).into()
+    }
 }
 
 fn value_for_char(chr: &char) -> i32 {
@@ -16,7 +28,7 @@ fn value_for_char(chr: &char) -> i32 {
     }
 }
 
-fn part_one(input: Vec<String>) -> i32 {
+fn part_one(input: &[String]) -> i32 {
     input
         .iter()
         .map(|line| {
@@ -31,7 +43,7 @@ fn part_one(input: Vec<String>) -> i32 {
         .sum()
 }
 
-fn part_two(input: Vec<String>) -> i32 {
+fn part_two(input: &[String]) -> i32 {
     input
         .chunks(3)
         .map(|group| {
@@ -68,12 +80,12 @@ CrZsJsPPZsGzwwsLwLmpwMDw
     #[test]
     fn part_one() {
         let x = input::test_vec(TEST_INPUT);
-        assert_eq!(super::part_one(x), SOLUTION_ONE);
+        assert_eq!(super::part_one(&x), SOLUTION_ONE);
     }
 
     #[test]
     fn part_two() {
         let x = input::test_vec(TEST_INPUT);
-        assert_eq!(super::part_two(x), SOLUTION_TWO);
+        assert_eq!(super::part_two(&x), SOLUTION_TWO);
     }
 }
@@ -1,13 +1,24 @@
-use crate::input;
+use super::{Answer, Solver};
 
-pub fn solve() {
-    let x = input::raw_file_for_day(5);
+pub struct Day05;
 
-    println!("Solution part 1: {}", part_one(x.clone()));
-    println!("Solution part 2: {}", part_two(x));
+impl Solver for Day05 {
+    type Input = String;
+
+    fn parse(raw: &str) -> Self::Input {
+        raw.to_string()
+    }
+
+    fn part_one(input: &Self::Input) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(input: &Self::Input) -> Answer {
+        part_two(input).into()
+    }
 }
 
-fn part_one(input: String) -> String {
+fn part_one(input: &str) -> String {
     let predicate = |columns: &mut Vec<Vec<char>>, count: usize, from: usize, to: usize| {
         for _ in 0..count {
             let val = columns[from].remove(0);
@@ -18,7 +29,7 @@ fn part_one(input: String) -> String {
     parse_with_predicate(input, predicate)
 }
 
-fn part_two(input: String) -> String {
+fn part_two(input: &str) -> String {
     let predicate = |columns: &mut Vec<Vec<char>>, count: usize, from: usize, to: usize| {
         for i in (0..count).rev() {
             let val = columns[from].remove(i);
@@ -29,7 +40,7 @@ fn part_two(input: String) -> String {
     parse_with_predicate(input, predicate)
 }
 
-fn parse_with_predicate<F>(input: String, predicate: F) -> String
+fn parse_with_predicate<F>(input: &str, predicate: F) -> String
 where
     F: Fn(&mut Vec<Vec<char>>, usize, usize, usize),
 {
@@ -93,12 +104,12 @@ move 1 from 1 to 2"#;
     #[test]
     fn part_one() {
         let x = input::test_vec_raw(TEST_INPUT);
-        assert_eq!(super::part_one(x), SOLUTION_ONE);
+        assert_eq!(super::part_one(&x), SOLUTION_ONE);
     }
 
     #[test]
     fn part_two() {
         let x = input::test_vec_raw(TEST_INPUT);
-        assert_eq!(super::part_two(x), SOLUTION_TWO);
+        assert_eq!(super::part_two(&x), SOLUTION_TWO);
     }
 }
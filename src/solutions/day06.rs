@@ -1,28 +1,32 @@
-use crate::input;
+use super::{Answer, Solver};
 
-pub fn solve() {
-    let x = input::raw_file_for_day(6);
+pub struct Day06;
 
-    let start = std::time::Instant::now();
-    let part1 = part_one(x.clone());
-    let duration = start.elapsed();
-    println!("Solution part 1: {} ({:?})", part1, duration);
+impl Solver for Day06 {
+    type Input = String;
 
-    let start = std::time::Instant::now();
-    let part2 = part_two(x);
-    let duration = start.elapsed();
-    println!("Solution part 2: {} ({:?})", part2, duration);
+    fn parse(raw: &str) -> Self::Input {
+        raw.to_string()
+    }
+
+    fn part_one(input: &Self::Input) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(input: &Self::Input) -> Answer {
+        part_two(input).into()
+    }
 }
 
-fn part_one(input: String) -> i32 {
+fn part_one(input: &str) -> i32 {
     find_unique_consecutives(input, 4)
 }
 
-fn part_two(input: String) -> i32 {
+fn part_two(input: &str) -> i32 {
     find_unique_consecutives(input, 14)
 }
 
-fn find_unique_consecutives(input: String, count: usize) -> i32 {
+fn find_unique_consecutives(input: &str, count: usize) -> i32 {
     let mut acc: std::collections::VecDeque<char> =
         std::collections::VecDeque::with_capacity(count);
 
@@ -58,7 +62,7 @@ mod tests {
         ];
 
         for (case, expect) in cases {
-            assert_eq!(super::part_one(case.to_string()), expect);
+            assert_eq!(super::part_one(case), expect);
         }
     }
 
@@ -73,7 +77,7 @@ mod tests {
         ];
 
         for (case, expect) in cases {
-            assert_eq!(super::part_two(case.to_string()), expect);
+            assert_eq!(super::part_two(case), expect);
         }
     }
 }
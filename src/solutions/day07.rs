@@ -1,11 +1,23 @@
+use super::{Answer, Solver};
 use crate::input;
 use std::collections::{HashMap, HashSet};
 
-pub fn solve() {
-    let x = input::file_for_day(7);
+pub struct Day07;
 
-    println!("Solution part 1: {}", part_one(x.clone()));
-    println!("Solution part 2: {}", part_two(x));
+impl Solver for Day07 {
+    type Input = Vec<String>;
+
+    fn parse(raw: &str) -> Self::Input {
+        input::lines(raw)
+    }
+
+    fn part_one(input: &Self::Input) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(input: &Self::Input) -> Answer {
+        part_two(input).into()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -33,7 +45,7 @@ impl Dir {
     }
 }
 
-fn part_one(input: Vec<String>) -> i64 {
+fn part_one(input: &[String]) -> i64 {
     let tree = get_tree(input);
 
     tree.values().fold(0, |acc, v| {
@@ -46,7 +58,7 @@ fn part_one(input: Vec<String>) -> i64 {
     })
 }
 
-fn part_two(input: Vec<String>) -> i64 {
+fn part_two(input: &[String]) -> i64 {
     let tree = get_tree(input);
     let used = tree.get("/").unwrap().size(&tree);
     let free = 70000000 - used;
@@ -64,7 +76,7 @@ fn part_two(input: Vec<String>) -> i64 {
         .unwrap()
 }
 
-fn get_tree(input: Vec<String>) -> HashMap<String, Dir> {
+fn get_tree(input: &[String]) -> HashMap<String, Dir> {
     let mut cwd = Dir::new();
     let mut tree: HashMap<String, Dir> = HashMap::new();
     let mut path = vec!["/".to_string()];
@@ -139,12 +151,12 @@ $ ls
     #[test]
     fn part_one() {
         let x = input::test_vec(TEST_INPUT);
-        assert_eq!(super::part_one(x), SOLUTION_ONE);
+        assert_eq!(super::part_one(&x), SOLUTION_ONE);
     }
 
     #[test]
     fn part_two() {
         let x = input::test_vec(TEST_INPUT);
-        assert_eq!(super::part_two(x), SOLUTION_TWO);
+        assert_eq!(super::part_two(&x), SOLUTION_TWO);
     }
 }
@@ -1,76 +1,46 @@
+use super::{Answer, Solver};
+use crate::geometry::{Direction, Point};
 use crate::input;
 use std::collections::HashSet;
 
-pub fn solve() {
-    let x = input::file_for_day(9);
+pub struct Day09;
 
-    println!("Solution part 1: {}", part_one(x.clone()));
-    println!("Solution part 2: {}", part_two(x));
-}
+impl Solver for Day09 {
+    type Input = Vec<String>;
 
-#[derive(Debug)]
-enum Direction {
-    Up,
-    UpRight,
-    UpLeft,
-    Down,
-    DownRight,
-    DownLeft,
-    Left,
-    Right,
-}
+    fn parse(raw: &str) -> Self::Input {
+        input::lines(raw)
+    }
 
-impl Direction {
-    fn step(&self) -> (i32, i32) {
-        match self {
-            Direction::Up => (-1, 0),
-            Direction::UpRight => (-1, 1),
-            Direction::UpLeft => (-1, -1),
-            Direction::Down => (1, 0),
-            Direction::DownRight => (1, 1),
-            Direction::DownLeft => (1, -1),
-            Direction::Left => (0, -1),
-            Direction::Right => (0, 1),
-        }
+    fn part_one(input: &Self::Input) -> Answer {
+        part_one(input).into()
     }
 
-    fn walk(&self, (from_x, from_y): (i32, i32)) -> (i32, i32) {
-        let (xd, yd) = self.step();
-        (from_x + xd, from_y + yd)
+    fn part_two(input: &Self::Input) -> Answer {
+        part_two(input).into()
     }
 }
 
-fn approach((from_x, from_y): (i32, i32), (target_x, target_y): (i32, i32)) -> (i32, i32) {
-    if (target_x - from_x).abs() <= 1 && (target_y - from_y).abs() <= 1 {
-        return (from_x, from_y);
+fn approach(from: Point, target: Point) -> Point {
+    if from.chebyshev(target) <= 1 {
+        return from;
     }
 
-    match (target_x, target_y) {
-        (x, y) if y == from_y && x < from_x => Direction::Up.walk((from_x, from_y)),
-        (x, y) if y == from_y && x > from_x => Direction::Down.walk((from_x, from_y)),
-        (x, y) if x == from_x && y < from_y => Direction::Left.walk((from_x, from_y)),
-        (x, y) if x == from_x && y > from_y => Direction::Right.walk((from_x, from_y)),
-        (x, y) if x < from_x && y > from_y => Direction::UpRight.walk((from_x, from_y)),
-        (x, y) if x < from_x && y < from_y => Direction::UpLeft.walk((from_x, from_y)),
-        (x, y) if x > from_x && y > from_y => Direction::DownRight.walk((from_x, from_y)),
-        (x, y) if x > from_x && y < from_y => Direction::DownLeft.walk((from_x, from_y)),
-
-        _ => unreachable!(),
-    }
+    from + (target - from).signum()
 }
 
-fn part_one(input: Vec<String>) -> i32 {
-    move_rope(&input, 2)
+fn part_one(input: &[String]) -> i32 {
+    move_rope(input, 2)
 }
 
-fn part_two(input: Vec<String>) -> i32 {
-    move_rope(&input, 10)
+fn part_two(input: &[String]) -> i32 {
+    move_rope(input, 10)
 }
 
 fn move_rope(input: &[String], length: usize) -> i32 {
-    let mut rope = vec![(0, 0); length];
-    let mut visited: HashSet<(i32, i32)> = HashSet::new();
-    visited.insert((0, 0));
+    let mut rope = vec![Point::ORIGIN; length];
+    let mut visited: HashSet<Point> = HashSet::new();
+    visited.insert(Point::ORIGIN);
 
     input
         .iter()
@@ -78,17 +48,11 @@ fn move_rope(input: &[String], length: usize) -> i32 {
             let (dir, ns) = line.split_once(' ').unwrap();
             let n = ns.parse::<i32>().unwrap();
 
-            match dir {
-                "U" => (Direction::Up, n),
-                "D" => (Direction::Down, n),
-                "L" => (Direction::Left, n),
-                "R" => (Direction::Right, n),
-                _ => unreachable!(),
-            }
+            (dir.parse::<Direction>().unwrap(), n)
         })
         .for_each(|(dir, steps)| {
             for _ in 0..steps {
-                rope[0] = dir.walk(rope[0]);
+                rope[0] += dir.step();
 
                 for knot in 1..rope.len() {
                     let this = rope[knot];
@@ -105,19 +69,19 @@ fn move_rope(input: &[String], length: usize) -> i32 {
 }
 
 #[allow(dead_code)]
-fn show(size: i32, visited: &HashSet<(i32, i32)>, rope: &[(i32, i32)]) {
+fn show(size: i32, visited: &HashSet<Point>, rope: &[Point]) {
     print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
 
-    for x in -size..=size {
-        for y in -size..=size {
-            let seen = visited.get(&(x, y));
+    for y in -size..=size {
+        for x in -size..=size {
+            let p = Point::new(x, y);
 
-            let c = match (x, y) {
-                (a, b) if a == 0 && b == 0 => "⭐️",
-                (a, b) if rope[0] == (a, b) => "🤖",
-                (a, b) if rope.contains(&(a, b)) => "💢",
-                (_, _) if seen.is_some() => "⬛️",
-                (_, _) => "  ",
+            let c = match p {
+                Point::ORIGIN => "⭐️",
+                _ if rope[0] == p => "🤖",
+                _ if rope.contains(&p) => "💢",
+                _ if visited.contains(&p) => "⬛️",
+                _ => "  ",
             };
 
             print!("{}", c);
@@ -155,12 +119,12 @@ U 20"#;
     #[test]
     fn part_one() {
         let x = input::test_vec(TEST_INPUT_ONE);
-        assert_eq!(super::part_one(x), SOLUTION_ONE);
+        assert_eq!(super::part_one(&x), SOLUTION_ONE);
     }
 
     #[test]
     fn part_two() {
         let x = input::test_vec(TEST_INPUT_TWO);
-        assert_eq!(super::part_two(x), SOLUTION_TWO);
+        assert_eq!(super::part_two(&x), SOLUTION_TWO);
     }
 }
@@ -1,14 +1,26 @@
+use super::{Answer, Solver};
 use crate::input;
 use std::collections::HashMap;
 
-pub fn solve() {
-    let x = input::file_for_day(10);
+pub struct Day10;
 
-    println!("Solution part 1: {}", part_one(x.clone()));
-    println!("Solution part 2: {}", part_two(x));
+impl Solver for Day10 {
+    type Input = Vec<String>;
+
+    fn parse(raw: &str) -> Self::Input {
+        input::lines(raw)
+    }
+
+    fn part_one(input: &Self::Input) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(input: &Self::Input) -> Answer {
+        part_two(input).into()
+    }
 }
 
-fn part_one(input: Vec<String>) -> i32 {
+fn part_one(input: &[String]) -> i32 {
     let mut register = 1;
     let mut cycle = 0;
     let mut cycle_value_map: HashMap<i32, i32> = HashMap::new();
@@ -43,7 +55,7 @@ fn part_one(input: Vec<String>) -> i32 {
     cycle_value_map.values().sum()
 }
 
-fn part_two(input: Vec<String>) -> i32 {
+fn part_two(input: &[String]) -> Vec<String> {
     let mut register = 1;
     let mut cycle = 0;
     let mut crt: Vec<&str> = Vec::new();
@@ -79,14 +91,7 @@ fn part_two(input: Vec<String>) -> i32 {
             }
         });
 
-    for (i, pixel) in crt.iter().enumerate() {
-        print!("{}", pixel);
-        if (i + 1) % 40 == 0 {
-            println!();
-        }
-    }
-
-    0
+    crt.chunks(40).map(|row| row.concat()).collect()
 }
 
 #[cfg(test)]
@@ -94,7 +99,14 @@ mod tests {
     use crate::input;
 
     static SOLUTION_ONE: i32 = 13140;
-    static SOLUTION_TWO: i32 = 0;
+    static SOLUTION_TWO: [&str; 6] = [
+        "##..##..##..##..##..##..##..##..##..##..",
+        "###...###...###...###...###...###...###.",
+        "####....####....####....####....####....",
+        "#####.....#####.....#####.....#####.....",
+        "######......######......######......####",
+        "#######.......#######.......#######.....",
+    ];
     static TEST_INPUT: &str = r#"
 addx 15
 addx -11
@@ -246,12 +258,17 @@ noop"#;
     #[test]
     fn part_one() {
         let x = input::test_vec(TEST_INPUT);
-        assert_eq!(super::part_one(x), SOLUTION_ONE);
+        assert_eq!(super::part_one(&x), SOLUTION_ONE);
     }
 
     #[test]
     fn part_two() {
         let x = input::test_vec(TEST_INPUT);
-        assert_eq!(super::part_two(x), SOLUTION_TWO);
+        let expected = SOLUTION_TWO
+            .iter()
+            .map(|row| row.replace('#', "█").replace('.', " "))
+            .collect::<Vec<_>>();
+
+        assert_eq!(super::part_two(&x), expected);
     }
 }
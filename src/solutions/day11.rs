@@ -1,38 +1,414 @@
-use crate::input;
+use super::{Answer, Solver};
+use num_bigint::BigInt;
+use num_integer::Integer;
 
-pub fn solve() {
-    let x = input::raw_file_for_day(11);
+pub struct Day11;
 
-    println!("Solution part 1: {}", part_one(x.clone()));
-    println!("Solution part 2: {}", part_two(x));
+impl Solver for Day11 {
+    type Input = Vec<Instruction>;
+
+    fn parse(raw: &str) -> Self::Input {
+        raw.split("\n\n").map(Instruction::new).collect()
+    }
+
+    fn part_one(monkeys: &Self::Input) -> Answer {
+        part_one(monkeys).into()
+    }
+
+    fn part_two(monkeys: &Self::Input) -> Answer {
+        part_two(monkeys).into()
+    }
 }
 
-fn part_one(input: String) -> i64 {
-    monkey_business(&input, |wl, _| wl / 3, 20)
+/// Twenty rounds is few enough to follow the worry levels exactly, so should they ever outgrow
+/// an `i64` we start over with big integers rather than give up. This is the way to run with
+/// exact worry levels, since the modulo trick can't be combined with dividing by three.
+fn part_one(monkeys: &[Instruction]) -> i64 {
+    match monkey_business::<i64>(monkeys, Relief::Divide(3), 20) {
+        Err(SimulationError::OutOfRange { .. }) => {
+            monkey_business::<BigInt>(monkeys, Relief::Divide(3), 20)
+        }
+        result => result,
+    }
+    .unwrap_or_else(|e| panic!("{}", e))
 }
 
-fn part_two(input: String) -> i64 {
-    monkey_business(&input, |wl, d| wl % d, 10000)
+fn part_two(monkeys: &[Instruction]) -> i64 {
+    monkey_business::<i64>(monkeys, Relief::Modulo, 10000).unwrap_or_else(|e| panic!("{}", e))
 }
 
-#[derive(Debug, Clone, PartialEq)]
+/// How worry levels are brought back down after each inspection.
+#[derive(Debug, Clone, Copy)]
+enum Relief {
+    /// Divide by the given number, rounding down.
+    Divide(i64),
+    /// Keep worry levels modulo the product of all the divisibility tests. That doesn't change
+    /// where any item is thrown, but only as long as no operation divides.
+    Modulo,
+}
+
+impl Relief {
+    fn apply<W: Worry>(self, worry_level: W, product: i64) -> Result<W, EvalError> {
+        match self {
+            Relief::Divide(n) => worry_level.try_div(&W::from_i64(n)),
+            Relief::Modulo => Ok(W::from_i64(worry_level.remainder(product))),
+        }
+    }
+}
+
+/// A worry level. Plain `i64` is fast and reports an overflow instead of wrapping around, while
+/// `BigInt` is exact however large the worry levels grow, which is only feasible for a handful
+/// of rounds unless something keeps them small. Both may go negative through subtraction.
+/// Operations return `None` when the result overflows an `i64`.
+trait Worry: Clone + std::fmt::Display {
+    fn from_i64(n: i64) -> Self;
+    fn is_zero(&self) -> bool;
+    fn try_add(&self, other: &Self) -> Option<Self>;
+    fn try_sub(&self, other: &Self) -> Option<Self>;
+    fn try_mul(&self, other: &Self) -> Option<Self>;
+    /// Divide rounding down, which only goes out of range for `i64::MIN / -1`.
+    fn checked_quotient(&self, other: &Self) -> Option<Self>;
+    fn remainder(&self, n: i64) -> i64;
+
+    fn try_div(&self, other: &Self) -> Result<Self, EvalError> {
+        if other.is_zero() {
+            return Err(EvalError::DivisionByZero);
+        }
+
+        self.checked_quotient(other).ok_or(EvalError::OutOfRange)
+    }
+}
+
+impl Worry for i64 {
+    fn from_i64(n: i64) -> Self {
+        n
+    }
+
+    fn is_zero(&self) -> bool {
+        *self == 0
+    }
+
+    fn try_add(&self, other: &Self) -> Option<Self> {
+        i64::checked_add(*self, *other)
+    }
+
+    fn try_sub(&self, other: &Self) -> Option<Self> {
+        i64::checked_sub(*self, *other)
+    }
+
+    fn try_mul(&self, other: &Self) -> Option<Self> {
+        i64::checked_mul(*self, *other)
+    }
+
+    fn checked_quotient(&self, other: &Self) -> Option<Self> {
+        // `checked_div` rounds towards zero, it's only here to catch the overflow.
+        i64::checked_div(*self, *other)?;
+        Some(Integer::div_floor(self, other))
+    }
+
+    fn remainder(&self, n: i64) -> i64 {
+        self % n
+    }
+}
+
+impl Worry for BigInt {
+    fn from_i64(n: i64) -> Self {
+        BigInt::from(n)
+    }
+
+    fn is_zero(&self) -> bool {
+        *self == BigInt::ZERO
+    }
+
+    fn try_add(&self, other: &Self) -> Option<Self> {
+        Some(self + other)
+    }
+
+    fn try_sub(&self, other: &Self) -> Option<Self> {
+        Some(self - other)
+    }
+
+    fn try_mul(&self, other: &Self) -> Option<Self> {
+        Some(self * other)
+    }
+
+    fn checked_quotient(&self, other: &Self) -> Option<Self> {
+        Some(self.div_floor(other))
+    }
+
+    fn remainder(&self, n: i64) -> i64 {
+        i64::try_from(self % n).unwrap()
+    }
+}
+
+/// Why an operation or relief couldn't produce a new worry level.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum EvalError {
+    OutOfRange,
+    DivisionByZero,
+}
+
+#[derive(Debug, PartialEq)]
+enum SimulationError {
+    OutOfRange {
+        round: usize,
+        monkey: usize,
+        worry_level: String,
+    },
+    DivisionByZero {
+        round: usize,
+        monkey: usize,
+        worry_level: String,
+    },
+    /// Taking worry levels modulo the divisibility tests is only sound for operations that add,
+    /// subtract and multiply, since `(a mod m) / b` is generally not `(a / b) mod m`.
+    DivisionWithModulo { monkey: usize },
+}
+
+impl SimulationError {
+    fn inspecting(error: EvalError, round: usize, monkey: usize, worry_level: String) -> Self {
+        match error {
+            EvalError::OutOfRange => SimulationError::OutOfRange {
+                round,
+                monkey,
+                worry_level,
+            },
+            EvalError::DivisionByZero => SimulationError::DivisionByZero {
+                round,
+                monkey,
+                worry_level,
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for SimulationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            SimulationError::OutOfRange {
+                round,
+                monkey,
+                worry_level,
+            } => write!(
+                f,
+                "worry level out of range in round {} when monkey {} inspected an item at {}",
+                round, monkey, worry_level
+            ),
+            SimulationError::DivisionByZero {
+                round,
+                monkey,
+                worry_level,
+            } => write!(
+                f,
+                "division by zero in round {} when monkey {} inspected an item at {}",
+                round, monkey, worry_level
+            ),
+            SimulationError::DivisionWithModulo { monkey } => write!(
+                f,
+                "monkey {} divides its worry level, which can't be kept modulo the tests",
+                monkey
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 enum Operator {
     Add,
+    Subtract,
     Multiply,
+    Divide,
 }
 
-#[derive(Debug, Clone)]
-enum OperatorValue {
+/// The right hand side of a monkey's `Operation: new = ...` line, in terms of the old worry
+/// level. Multiplication and division bind tighter than addition and subtraction, operators of
+/// the same precedence apply left to right, and parentheses group as usual.
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
     Old,
-    N(i64),
+    Number(i64),
+    Binary(Box<Expr>, Operator, Box<Expr>),
+}
+
+impl Expr {
+    /// The new worry level, or why it couldn't be computed in `W`.
+    fn evaluate<W: Worry>(&self, old: &W) -> Result<W, EvalError> {
+        match self {
+            Expr::Old => Ok(old.clone()),
+            Expr::Number(n) => Ok(W::from_i64(*n)),
+            Expr::Binary(lhs, operator, rhs) => {
+                let (lhs, rhs) = (lhs.evaluate(old)?, rhs.evaluate(old)?);
+
+                match operator {
+                    Operator::Add => lhs.try_add(&rhs).ok_or(EvalError::OutOfRange),
+                    Operator::Subtract => lhs.try_sub(&rhs).ok_or(EvalError::OutOfRange),
+                    Operator::Multiply => lhs.try_mul(&rhs).ok_or(EvalError::OutOfRange),
+                    Operator::Divide => lhs.try_div(&rhs),
+                }
+            }
+        }
+    }
+
+    fn divides(&self) -> bool {
+        match self {
+            Expr::Old | Expr::Number(_) => false,
+            Expr::Binary(lhs, operator, rhs) => {
+                *operator == Operator::Divide || lhs.divides() || rhs.divides()
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct ParseExprError(String);
+
+impl std::fmt::Display for ParseExprError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token {
+    Old,
+    Number(i64),
+    Operator(Operator),
+    Open,
+    Close,
+}
+
+impl std::str::FromStr for Expr {
+    type Err = ParseExprError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut tokens = Vec::new();
+        let mut chars = s.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            let token = match c {
+                c if c.is_whitespace() => continue,
+                '+' => Token::Operator(Operator::Add),
+                '-' => Token::Operator(Operator::Subtract),
+                '*' => Token::Operator(Operator::Multiply),
+                '/' => Token::Operator(Operator::Divide),
+                '(' => Token::Open,
+                ')' => Token::Close,
+                c if c.is_ascii_digit() => {
+                    let mut digits = c.to_string();
+                    while let Some(d) = chars.next_if(char::is_ascii_digit) {
+                        digits.push(d);
+                    }
+
+                    let n = digits.parse().map_err(|_| {
+                        ParseExprError(format!("number `{}` is too large", digits))
+                    })?;
+
+                    Token::Number(n)
+                }
+                c if c.is_alphabetic() => {
+                    let mut word = c.to_string();
+                    while let Some(l) = chars.next_if(|c| c.is_alphabetic()) {
+                        word.push(l);
+                    }
+
+                    if word != "old" {
+                        return Err(ParseExprError(format!("unknown variable `{}`", word)));
+                    }
+
+                    Token::Old
+                }
+                c => return Err(ParseExprError(format!("unexpected character `{}`", c))),
+            };
+
+            tokens.push(token);
+        }
+
+        let mut parser = ExprParser {
+            tokens,
+            position: 0,
+        };
+        let expr = parser.expression()?;
+
+        match parser.next() {
+            None => Ok(expr),
+            Some(_) => Err(ParseExprError(format!(
+                "unexpected token after the end of the expression in `{}`",
+                s.trim()
+            ))),
+        }
+    }
+}
+
+/// A recursive descent parser over the grammar
+///
+/// expression = term { ("+" | "-") term }
+/// term = factor { ("*" | "/") factor }
+/// factor = "old" | number | "(" expression ")"
+struct ExprParser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl ExprParser {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).copied();
+        self.position += 1;
+
+        token
+    }
+
+    /// Parse operands separated by any of `operators`, folding them to the left.
+    fn binary<F>(&mut self, operators: &[Operator], operand: F) -> Result<Expr, ParseExprError>
+    where
+        F: Fn(&mut Self) -> Result<Expr, ParseExprError>,
+    {
+        let mut lhs = operand(self)?;
+
+        while let Some(&Token::Operator(operator)) = self.tokens.get(self.position) {
+            if !operators.contains(&operator) {
+                break;
+            }
+
+            self.position += 1;
+            lhs = Expr::Binary(Box::new(lhs), operator, Box::new(operand(self)?));
+        }
+
+        Ok(lhs)
+    }
+
+    fn expression(&mut self) -> Result<Expr, ParseExprError> {
+        self.binary(&[Operator::Add, Operator::Subtract], Self::term)
+    }
+
+    fn term(&mut self) -> Result<Expr, ParseExprError> {
+        self.binary(&[Operator::Multiply, Operator::Divide], Self::factor)
+    }
+
+    fn factor(&mut self) -> Result<Expr, ParseExprError> {
+        match self.next() {
+            Some(Token::Old) => Ok(Expr::Old),
+            Some(Token::Number(n)) => Ok(Expr::Number(n)),
+            Some(Token::Open) => {
+                let expr = self.expression()?;
+
+                match self.next() {
+                    Some(Token::Close) => Ok(expr),
+                    _ => Err(ParseExprError("missing closing parenthesis".to_string())),
+                }
+            }
+            Some(_) => Err(ParseExprError(
+                "expected `old`, a number or an opening parenthesis".to_string(),
+            )),
+            None => Err(ParseExprError("unexpected end of expression".to_string())),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
-struct Instruction {
+pub struct Instruction {
     monkey: usize,
     items: Vec<i64>,
-    operation: Operator,
-    operation_val: OperatorValue,
+    operation: Expr,
     test_divisible_by: i64,
     throw_if_true: usize,
     throw_if_false: usize,
@@ -43,8 +419,7 @@ impl Instruction {
         Self {
             monkey: 0,
             items: vec![],
-            operation: Operator::Add,
-            operation_val: OperatorValue::Old,
+            operation: Expr::Old,
             test_divisible_by: 0,
             throw_if_true: 0,
             throw_if_false: 0,
@@ -72,19 +447,11 @@ impl Instruction {
                     .collect()
             }
             x if x.starts_with("Operation:") => {
-                let (_, expression) = line.split_once("old ").unwrap();
-                let (o, n) = expression.split_once(' ').unwrap();
-
-                match o {
-                    "+" => i.operation = Operator::Add,
-                    "*" => i.operation = Operator::Multiply,
-                    _ => unreachable!(),
-                }
+                let (_, expression) = line.split_once('=').unwrap();
 
-                match n {
-                    "old" => i.operation_val = OperatorValue::Old,
-                    n => i.operation_val = OperatorValue::N(n.parse().unwrap()),
-                }
+                i.operation = expression
+                    .parse()
+                    .unwrap_or_else(|e| panic!("invalid operation `{}`: {}", x, e));
             }
             x if x.starts_with("Test:") => {
                 i.test_divisible_by = x.split(' ').nth(3).unwrap().parse().unwrap()
@@ -102,50 +469,59 @@ impl Instruction {
     }
 }
 
-fn monkey_business<F>(input: &str, f: F, steps: usize) -> i64
-where
-    F: Fn(i64, i64) -> i64,
-{
-    let mut monkeys = input
-        .split("\n\n")
-        .map(Instruction::new)
+/// Simulate `rounds` rounds, applying `relief` to each worry level after inspection.
+fn monkey_business<W: Worry>(
+    monkeys: &[Instruction],
+    relief: Relief,
+    rounds: usize,
+) -> Result<i64, SimulationError> {
+    if let Relief::Modulo = relief {
+        if let Some(monkey) = monkeys.iter().find(|m| m.operation.divides()) {
+            return Err(SimulationError::DivisionWithModulo {
+                monkey: monkey.monkey,
+            });
+        }
+    }
+
+    let mut items = monkeys
+        .iter()
+        .map(|m| m.items.iter().map(|&n| W::from_i64(n)).collect::<Vec<_>>())
         .collect::<Vec<_>>();
     let mut monkey_inspection: Vec<i64> = vec![0; monkeys.len()];
     let div = monkeys.iter().map(|m| m.test_divisible_by).product::<i64>();
 
-    for _ in 0..steps {
-        for current_monkey in 0..monkeys.len() {
-            let monkey = monkeys[current_monkey].clone();
-            monkey_inspection[current_monkey] += monkey.items.len() as i64;
-
-            for item in monkey.items {
-                let wl = match (&monkey.operation, &monkey.operation_val) {
-                    (Operator::Add, OperatorValue::N(n)) => item + n,
-                    (Operator::Add, OperatorValue::Old) => item + item,
-                    (Operator::Multiply, OperatorValue::N(n)) => item * n,
-                    (Operator::Multiply, OperatorValue::Old) => item * item,
-                };
+    for round in 1..=rounds {
+        for monkey in monkeys {
+            let held = std::mem::take(&mut items[monkey.monkey]);
+            monkey_inspection[monkey.monkey] += held.len() as i64;
+
+            for item in held {
+                let worry_level = monkey
+                    .operation
+                    .evaluate(&item)
+                    .and_then(|wl| relief.apply(wl, div))
+                    .map_err(|e| {
+                        SimulationError::inspecting(e, round, monkey.monkey, item.to_string())
+                    })?;
 
-                let worry_level = f(wl, div);
-                let monkey_idx = if worry_level % monkey.test_divisible_by == 0 {
+                let monkey_idx = if worry_level.remainder(monkey.test_divisible_by) == 0 {
                     monkey.throw_if_true
                 } else {
                     monkey.throw_if_false
                 };
 
-                monkeys[monkey_idx].items.push(worry_level);
+                items[monkey_idx].push(worry_level);
             }
-
-            monkeys[monkey.monkey].items.clear();
         }
     }
 
     monkey_inspection.sort_by(|a, b| b.cmp(a));
-    monkey_inspection.iter().take(2).product()
+    Ok(monkey_inspection.iter().take(2).product())
 }
 
 #[cfg(test)]
 mod tests {
+    use super::{Day11, Solver};
     use crate::input;
 
     static SOLUTION_ONE: i64 = 10605;
@@ -181,13 +557,98 @@ Monkey 3:
 
     #[test]
     fn part_one() {
-        let x = input::test_vec_raw(TEST_INPUT);
-        assert_eq!(super::part_one(x), SOLUTION_ONE);
+        let x = Day11::parse(&input::test_vec_raw(TEST_INPUT));
+        assert_eq!(super::part_one(&x), SOLUTION_ONE);
     }
 
     #[test]
     fn part_two() {
-        let x = input::test_vec_raw(TEST_INPUT);
-        assert_eq!(super::part_two(x), SOLUTION_TWO);
+        let x = Day11::parse(&input::test_vec_raw(TEST_INPUT));
+        assert_eq!(super::part_two(&x), SOLUTION_TWO);
+    }
+
+    #[test]
+    fn expressions() {
+        use super::{EvalError, Expr, ParseExprError};
+        use num_bigint::BigInt;
+
+        let eval = |s: &str, old: i64| s.parse::<Expr>().unwrap().evaluate(&old);
+
+        assert_eq!(eval("old * 19", 2), Ok(38));
+        assert_eq!(eval("2 + 3 * old", 4), Ok(14));
+        assert_eq!(eval("(2 + 3) * old", 4), Ok(20));
+        assert_eq!(eval("old - 20 - 3", 10), Ok(-13));
+        assert_eq!(eval("old * old - (old + 3) / 2", 10), Ok(94));
+        assert_eq!(eval("(old - 20) / 3", 10), Ok(-4));
+        assert_eq!(eval("old / (old - old)", 10), Err(EvalError::DivisionByZero));
+        assert_eq!(eval("old * old", i64::MAX), Err(EvalError::OutOfRange));
+
+        let big = "old - 20".parse::<Expr>().unwrap();
+        assert_eq!(big.evaluate(&BigInt::from(10)), Ok(BigInt::from(-10)));
+        assert_eq!(big.evaluate(&BigInt::from(30)), Ok(BigInt::from(10)));
+
+        let floor = "(old - 20) / 3".parse::<Expr>().unwrap();
+        assert_eq!(floor.evaluate(&BigInt::from(10)), Ok(BigInt::from(-4)));
+
+        assert_eq!(
+            "old +".parse::<Expr>(),
+            Err(ParseExprError("unexpected end of expression".to_string()))
+        );
+        assert_eq!(
+            "old ^ 2".parse::<Expr>(),
+            Err(ParseExprError("unexpected character `^`".to_string()))
+        );
+        assert_eq!(
+            "(old + 1".parse::<Expr>(),
+            Err(ParseExprError("missing closing parenthesis".to_string()))
+        );
+        assert!("old old".parse::<Expr>().is_err());
+        assert!("new + 1".parse::<Expr>().is_err());
+    }
+
+    #[test]
+    fn exact_worry_levels() {
+        use super::{monkey_business, Relief, SimulationError};
+        use num_bigint::BigInt;
+
+        // Without relief the worry levels explode, but twenty rounds are still few enough to
+        // follow them exactly and check that the modulo trick throws items the same way.
+        let x = Day11::parse(&input::test_vec_raw(TEST_INPUT));
+        let exact = monkey_business::<BigInt>(&x, Relief::Divide(1), 20);
+        let modulo = monkey_business::<i64>(&x, Relief::Modulo, 20);
+        assert_eq!(exact, Ok(99 * 103));
+        assert_eq!(exact, modulo);
+
+        let relieved = monkey_business::<BigInt>(&x, Relief::Divide(3), 20);
+        assert_eq!(relieved, Ok(SOLUTION_ONE));
+
+        let out_of_range = monkey_business::<i64>(&x, Relief::Divide(1), 20);
+        assert!(matches!(
+            out_of_range,
+            Err(SimulationError::OutOfRange { .. })
+        ));
+    }
+
+    #[test]
+    fn division() {
+        use super::{monkey_business, Relief, SimulationError};
+
+        let x = Day11::parse(&input::test_vec_raw(TEST_INPUT).replace("old + 3", "old / 2"));
+        assert_eq!(
+            monkey_business::<i64>(&x, Relief::Modulo, 20),
+            Err(SimulationError::DivisionWithModulo { monkey: 3 })
+        );
+        assert!(monkey_business::<i64>(&x, Relief::Divide(3), 20).is_ok());
+
+        let raw = input::test_vec_raw(TEST_INPUT).replace("old + 3", "old / (old - old)");
+        let x = Day11::parse(&raw);
+        assert_eq!(
+            monkey_business::<i64>(&x, Relief::Divide(3), 20),
+            Err(SimulationError::DivisionByZero {
+                round: 1,
+                monkey: 3,
+                worry_level: "74".to_string(),
+            })
+        );
     }
 }
@@ -1,201 +1,347 @@
-use crate::input;
+use super::{Answer, Solver};
+use std::cmp::Ordering;
 
-pub fn solve() {
-    let x = input::raw_file_for_day(13);
+pub struct Day13;
 
-    println!("Solution part 1: {}", part_one(x.clone()));
-    println!("Solution part 2: {}", part_two(x));
-}
-
-fn part_one(input: String) -> i64 {
-    let pairs = input
-        .split("\n\n")
-        .map(|group| {
-            let mut line = group.lines();
-            let left_chars = line.next().unwrap().chars().collect::<Vec<_>>();
-            let right_chars = line.next().unwrap().chars().collect::<Vec<_>>();
-            let (_, left) = parse(&left_chars, 0);
-            let (_, right) = parse(&right_chars, 0);
+impl Solver for Day13 {
+    type Input = Vec<(Packet, Packet)>;
 
-            (left[0].subpackets.clone(), right[0].subpackets.clone())
-        })
-        .collect::<Vec<_>>();
+    fn parse(raw: &str) -> Self::Input {
+        raw.split("\n\n")
+            .map(|group| {
+                let mut line = group.lines();
+                let left = packet(line.next().unwrap());
+                let right = packet(line.next().unwrap());
 
-    let mut indicies: Vec<usize> = vec![];
-    for (i, (lhs, rhs)) in pairs.iter().enumerate() {
-        let idx = i + 1;
-        let decider = compare_order(lhs, rhs);
+                (left, right)
+            })
+            .collect()
+    }
 
-        if decider != Order::Incorrect {
-            indicies.push(idx);
-        }
+    fn part_one(pairs: &Self::Input) -> Answer {
+        part_one(pairs).into()
     }
 
-    indicies.iter().sum::<usize>() as i64
+    fn part_two(pairs: &Self::Input) -> Answer {
+        part_two(pairs).into()
+    }
 }
 
-fn part_two(input: String) -> i64 {
-    let full_input = format!("{}\n[[2]]\n[[6]]", input);
-    let mut pairs = full_input
-        .lines()
-        .filter(|line| !line.is_empty())
-        .map(|line| {
-            let ch = line.chars().collect::<Vec<_>>();
-            let (_, mut pkt) = parse(&ch, 0);
+fn part_one(pairs: &[(Packet, Packet)]) -> i64 {
+    pairs
+        .iter()
+        .enumerate()
+        .filter(|(_, (lhs, rhs))| lhs <= rhs)
+        .map(|(i, _)| i as i64 + 1)
+        .sum()
+}
 
-            pkt[0].tag = match line {
-                "[[2]]" => Some(2),
-                "[[6]]" => Some(6),
-                _ => None,
-            };
+fn part_two(pairs: &[(Packet, Packet)]) -> i64 {
+    decoder_key(pairs.iter().flat_map(|(left, right)| [left, right]))
+}
 
-            pkt[0].clone()
+/// Where the divider packets end up once all packets are in order. There's no need to sort for
+/// that, a divider's position is just the number of packets smaller than it, counting the other
+/// divider.
+fn decoder_key<'a>(packets: impl Iterator<Item = &'a Packet> + Clone) -> i64 {
+    let dividers = [packet("[[2]]"), packet("[[6]]")];
+
+    dividers
+        .iter()
+        .map(|divider| {
+            let smaller = packets.clone().filter(|&p| p < divider).count()
+                + dividers.iter().filter(|&d| d < divider).count();
+            smaller as i64 + 1
         })
-        .collect::<Vec<_>>();
+        .product()
+}
 
-    for i in 0..pairs.len() {
-        for j in 0..pairs.len() - i - 1 {
-            if compare_order(&[pairs[j].clone()], &[pairs[j + 1].clone()]) == Order::Incorrect {
-                pairs.swap(j, j + 1);
-            }
-        }
-    }
+fn packet(line: &str) -> Packet {
+    line.parse()
+        .unwrap_or_else(|e| panic!("invalid packet `{}`: {}", line, e))
+}
+
+/// A packet is either an integer or a list of packets, which may be empty.
+///
+/// Packets are ordered by the puzzle rules: integers by value, lists element by element with the
+/// shorter list first if one runs out, and an integer compared to a list as if it were a list
+/// containing only that integer. Equality follows the same rules, so `[[1]]`, `[1]` and `1` are
+/// all equal.
+#[derive(Debug, Clone)]
+pub enum Packet {
+    Int(i64),
+    List(Vec<Packet>),
+}
 
-    let (mut a, mut b) = (0, 0);
-    for (i, pkt) in pairs.iter().enumerate() {
-        match pkt.tag {
-            Some(2) => a = i + 1,
-            Some(6) => b = i + 1,
-            _ => (),
+impl Ord for Packet {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Packet::Int(l), Packet::Int(r)) => l.cmp(r),
+            (Packet::List(l), Packet::List(r)) => l.cmp(r),
+            (Packet::List(l), Packet::Int(_)) => l.as_slice().cmp(std::slice::from_ref(other)),
+            (Packet::Int(_), Packet::List(r)) => std::slice::from_ref(self).cmp(r.as_slice()),
         }
     }
+}
 
-    (a * b) as i64
+impl PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
-#[derive(Debug, Clone)]
-struct Packet {
-    is_value: bool,
-    value: i32,
-    subpackets: Vec<Packet>,
-    tag: Option<i32>,
+impl PartialEq for Packet {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
 }
 
+impl Eq for Packet {}
+
 impl Packet {
-    fn new(v: i32) -> Self {
-        Self {
-            is_value: true,
-            value: v,
-            subpackets: vec![],
-            tag: None,
-        }
+    /// Compare two packets like `cmp`, but also explain each step of the comparison in the same
+    /// words as the puzzle description. Useful when an answer is off and it's not clear which
+    /// pair was ordered the wrong way.
+    #[allow(dead_code)]
+    fn trace(&self, other: &Packet) -> (Ordering, String) {
+        let mut lines = Vec::new();
+        let order = self.trace_into(other, 0, &mut lines);
+
+        (order, lines.join("\n"))
     }
 
-    fn new_subpacket(sub: Vec<Self>) -> Self {
-        Self {
-            is_value: false,
-            value: 0,
-            subpackets: sub,
-            tag: None,
+    fn trace_into(&self, other: &Packet, depth: usize, lines: &mut Vec<String>) -> Ordering {
+        fn note(lines: &mut Vec<String>, depth: usize, text: String) {
+            lines.push(format!("{:1$}- {text}", "", depth * 2));
+        }
+
+        note(lines, depth, format!("Compare {} vs {}", self, other));
+
+        let (order, ran_out) = match (self, other) {
+            (Packet::Int(l), Packet::Int(r)) => (l.cmp(r), "is smaller"),
+            (Packet::List(l), Packet::List(r)) => {
+                for (lhs, rhs) in l.iter().zip(r) {
+                    let order = lhs.trace_into(rhs, depth + 1, lines);
+                    if order != Ordering::Equal {
+                        return order;
+                    }
+                }
+
+                (l.len().cmp(&r.len()), "ran out of items")
+            }
+            (Packet::Int(_), Packet::List(_)) => {
+                note(
+                    lines,
+                    depth + 1,
+                    format!("Mixed types; convert left to [{}] and retry comparison", self),
+                );
+                return Packet::List(vec![self.clone()]).trace_into(other, depth + 1, lines);
+            }
+            (Packet::List(_), Packet::Int(_)) => {
+                note(
+                    lines,
+                    depth + 1,
+                    format!("Mixed types; convert right to [{}] and retry comparison", other),
+                );
+                return self.trace_into(&Packet::List(vec![other.clone()]), depth + 1, lines);
+            }
+        };
+
+        match order {
+            Ordering::Less => note(
+                lines,
+                depth + 1,
+                format!("Left side {ran_out}, so inputs are in the right order"),
+            ),
+            Ordering::Greater => note(
+                lines,
+                depth + 1,
+                format!("Right side {ran_out}, so inputs are not in the right order"),
+            ),
+            Ordering::Equal => (),
         }
+
+        order
     }
 
-    fn is_none(&self) -> bool {
-        !self.is_value && self.subpackets.is_empty()
+    fn write_tree(&self, f: &mut std::fmt::Formatter, depth: usize) -> std::fmt::Result {
+        let indent = depth * 2;
+
+        match self {
+            Packet::Int(n) => write!(f, "{:indent$}{}", "", n),
+            Packet::List(packets) if packets.is_empty() => write!(f, "{:indent$}[]", ""),
+            Packet::List(packets) => {
+                writeln!(f, "{:indent$}[", "")?;
+
+                for (i, packet) in packets.iter().enumerate() {
+                    packet.write_tree(f, depth + 1)?;
+
+                    if i + 1 < packets.len() {
+                        write!(f, ",")?;
+                    }
+
+                    writeln!(f)?;
+                }
+
+                write!(f, "{:indent$}]", "")
+            }
+        }
     }
+}
+
+/// Written back in the bracket notation of the input, or with `{:#}` as an indented tree with
+/// one integer or list per line. Both can be parsed again.
+impl std::fmt::Display for Packet {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if f.alternate() {
+            return self.write_tree(f, 0);
+        }
 
-    fn has_sub(&self) -> bool {
-        !self.subpackets.is_empty()
+        match self {
+            Packet::Int(n) => write!(f, "{}", n),
+            Packet::List(packets) => {
+                write!(f, "[")?;
+
+                for (i, packet) in packets.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+
+                    write!(f, "{}", packet)?;
+                }
+
+                write!(f, "]")
+            }
+        }
     }
 }
 
 #[derive(Debug, PartialEq)]
-enum Order {
-    Correct,
-    Incorrect,
-    Unknown,
+pub struct ParsePacketError {
+    /// Character offset into the line where parsing failed.
+    position: usize,
+    expected: &'static str,
+    found: Option<char>,
 }
 
-fn compare_order(l: &[Packet], r: &[Packet]) -> Order {
-    for (i, lhs) in l.iter().enumerate() {
-        if r.len() < i + 1 {
-            break;
-        }
+impl std::fmt::Display for ParsePacketError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "expected {} at position {}, ", self.expected, self.position)?;
 
-        let rhs = &r[i];
-        if rhs.is_none() && !lhs.is_none() {
-            return Order::Incorrect;
+        match self.found {
+            Some(c) => write!(f, "found `{}`", c),
+            None => write!(f, "found end of input"),
         }
+    }
+}
 
-        if lhs.is_none() && !rhs.is_none() {
-            return Order::Correct;
-        }
+impl std::str::FromStr for Packet {
+    type Err = ParsePacketError;
 
-        let order = match (lhs, rhs) {
-            (l, r) if lhs.is_value && rhs.is_value => {
-                match (l.value, r.value) {
-                    (l, r) if l == r => continue,
-                    (l, r) if l < r => return Order::Correct,
-                    (l, r) if l > r => return Order::Incorrect,
-                    (_, _) => unreachable!(),
-                };
-            }
-            (l, r) if lhs.has_sub() && rhs.has_sub() => compare_order(&l.subpackets, &r.subpackets),
-            (l, r) if lhs.has_sub() => compare_order(&l.subpackets, &[r.clone()]),
-            (l, r) if rhs.has_sub() => compare_order(&[l.clone()], &r.subpackets),
-            (_, _) => continue,
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            chars: s.chars().collect(),
+            position: 0,
         };
 
-        if order != Order::Unknown {
-            return order;
+        let packet = parser.packet()?;
+        parser.skip_whitespace();
+
+        match parser.peek() {
+            None => Ok(packet),
+            found => Err(parser.error("end of input", found)),
         }
     }
+}
+
+/// A recursive descent parser over the grammar
+///
+/// packet = integer | "[" [ packet { "," packet } ] "]"
+///
+/// with whitespace allowed between any two tokens.
+struct Parser {
+    chars: Vec<char>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
 
-    match l.len().cmp(&r.len()) {
-        std::cmp::Ordering::Less => Order::Correct,
-        std::cmp::Ordering::Greater => Order::Incorrect,
-        std::cmp::Ordering::Equal => Order::Unknown,
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.position += 1;
+        }
     }
-}
 
-fn parse(input: &[char], start_at: usize) -> (usize, Vec<Packet>) {
-    let mut pkts = Vec::new();
-    let mut i = start_at;
+    fn error(&self, expected: &'static str, found: Option<char>) -> ParsePacketError {
+        ParsePacketError {
+            position: self.position,
+            expected,
+            found,
+        }
+    }
 
-    while i < input.len() {
-        let item = input[i];
+    fn packet(&mut self) -> Result<Packet, ParsePacketError> {
+        self.skip_whitespace();
 
-        match item {
-            ',' => (),
-            ']' => return (i, pkts),
-            '[' => {
-                let (read_until, subpackets) = parse(input, i + 1);
-                i = read_until;
+        match self.peek() {
+            Some('[') => self.list(),
+            Some(c) if c.is_ascii_digit() => self.integer(),
+            found => Err(self.error("`[` or an integer", found)),
+        }
+    }
 
-                pkts.push(Packet::new_subpacket(subpackets));
-            }
-            c if c.is_ascii_digit() => {
-                // Yeah let's assume 10 is the largest number...
-                let value = if input[i + 1].is_ascii_digit() {
-                    i += 1;
-                    10
-                } else {
-                    c.to_digit(10).unwrap() as i32
-                };
-
-                pkts.push(Packet::new(value));
-            }
-            _ => unreachable!(),
+    fn list(&mut self) -> Result<Packet, ParsePacketError> {
+        // Skip the opening bracket.
+        self.position += 1;
+        self.skip_whitespace();
+
+        let mut packets = Vec::new();
+        if self.peek() == Some(']') {
+            self.position += 1;
+            return Ok(Packet::List(packets));
         }
 
-        i += 1;
+        loop {
+            packets.push(self.packet()?);
+            self.skip_whitespace();
+
+            match self.peek() {
+                Some(',') => self.position += 1,
+                Some(']') => {
+                    self.position += 1;
+                    return Ok(Packet::List(packets));
+                }
+                found => return Err(self.error("`,` or `]`", found)),
+            }
+        }
     }
 
-    (0, pkts)
+    fn integer(&mut self) -> Result<Packet, ParsePacketError> {
+        let start = self.position;
+        let mut value: i64 = 0;
+
+        while let Some(digit) = self.peek().and_then(|c| c.to_digit(10)) {
+            value = value
+                .checked_mul(10)
+                .and_then(|v| v.checked_add(digit as i64))
+                .ok_or(ParsePacketError {
+                    position: start,
+                    expected: "an integer that fits in 64 bits",
+                    found: self.chars.get(start).copied(),
+                })?;
+            self.position += 1;
+        }
+
+        Ok(Packet::Int(value))
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::{Day13, Solver};
     use crate::input;
 
     static SOLUTION_ONE: i64 = 13;
@@ -227,13 +373,125 @@ mod tests {
 
     #[test]
     fn part_one() {
-        let x = input::test_vec_raw(TEST_INPUT);
-        assert_eq!(super::part_one(x), SOLUTION_ONE);
+        let x = Day13::parse(&input::test_vec_raw(TEST_INPUT));
+        assert_eq!(super::part_one(&x), SOLUTION_ONE);
     }
 
     #[test]
     fn part_two() {
-        let x = input::test_vec_raw(TEST_INPUT);
-        assert_eq!(super::part_two(x), SOLUTION_TWO);
+        let x = Day13::parse(&input::test_vec_raw(TEST_INPUT));
+        assert_eq!(super::part_two(&x), SOLUTION_TWO);
+    }
+
+    #[test]
+    fn decoder_key() {
+        use super::{decoder_key, packet};
+
+        let mut packets = input::test_vec(TEST_INPUT)
+            .iter()
+            .map(|line| packet(line))
+            .collect::<Vec<_>>();
+
+        assert_eq!(decoder_key(packets.iter()), SOLUTION_TWO);
+
+        // Counting has to agree with where the dividers land when actually sorted.
+        let dividers = [packet("[[2]]"), packet("[[6]]")];
+        packets.extend(dividers.iter().cloned());
+        packets.sort();
+
+        let positions = dividers.map(|divider| packets.partition_point(|p| p < &divider) + 1);
+        assert_eq!(positions, [10, 14]);
+    }
+
+    #[test]
+    fn order() {
+        use super::packet;
+
+        assert!(packet("[1,1,3,1,1]") < packet("[1,1,5,1,1]"));
+        assert!(packet("[[1],[2,3,4]]") < packet("[[1],4]"));
+        assert!(packet("[9]") > packet("[[8,7,6]]"));
+        assert!(packet("[]") < packet("[3]"));
+        assert!(packet("[[[]]]") > packet("[[]]"));
+        assert_eq!(packet("[[2]]"), packet("2"));
+    }
+
+    #[test]
+    fn parse() {
+        use super::{Packet, ParsePacketError};
+
+        assert_eq!(
+            " [ 10, [], [123 ,4] ] ".parse(),
+            Ok(Packet::List(vec![
+                Packet::Int(10),
+                Packet::List(vec![]),
+                Packet::List(vec![Packet::Int(123), Packet::Int(4)]),
+            ]))
+        );
+        assert_eq!(
+            "[1,,2]".parse::<Packet>(),
+            Err(ParsePacketError {
+                position: 3,
+                expected: "`[` or an integer",
+                found: Some(','),
+            })
+        );
+        assert_eq!(
+            "[[1]".parse::<Packet>().unwrap_err().to_string(),
+            "expected `,` or `]` at position 4, found end of input"
+        );
+        assert_eq!(
+            "[1]]".parse::<Packet>().unwrap_err().to_string(),
+            "expected end of input at position 3, found `]`"
+        );
+    }
+
+    #[test]
+    fn display() {
+        use super::packet;
+
+        for line in input::test_vec(TEST_INPUT) {
+            let p = packet(&line);
+
+            assert_eq!(p.to_string(), line);
+            assert_eq!(packet(&format!("{:#}", p)).to_string(), line);
+        }
+
+        assert_eq!(
+            format!("{:#}", packet("[1,[],[2,[3]]]")),
+            "[\n  1,\n  [],\n  [\n    2,\n    [\n      3\n    ]\n  ]\n]"
+        );
+    }
+
+    #[test]
+    fn trace() {
+        use super::packet;
+        use std::cmp::Ordering;
+
+        let (order, trace) = packet("[[1],[2,3,4]]").trace(&packet("[[1],4]"));
+        assert_eq!(order, Ordering::Less);
+        assert_eq!(
+            trace,
+            r#"- Compare [[1],[2,3,4]] vs [[1],4]
+  - Compare [1] vs [1]
+    - Compare 1 vs 1
+  - Compare [2,3,4] vs 4
+    - Mixed types; convert right to [4] and retry comparison
+    - Compare [2,3,4] vs [4]
+      - Compare 2 vs 4
+        - Left side is smaller, so inputs are in the right order"#
+        );
+
+        let (order, trace) = packet("[7,7,7,7]").trace(&packet("[7,7,7]"));
+        assert_eq!(order, Ordering::Greater);
+        assert_eq!(
+            trace.lines().last(),
+            Some("  - Right side ran out of items, so inputs are not in the right order")
+        );
+
+        let lines = input::test_vec(TEST_INPUT);
+        for pair in lines.chunks(2) {
+            let (lhs, rhs) = (packet(&pair[0]), packet(&pair[1]));
+            assert_eq!(lhs.trace(&rhs).0, lhs.cmp(&rhs));
+        }
     }
 }
@@ -1,19 +1,32 @@
+use super::{Answer, Solver};
+use crate::geometry::Point;
+use crate::grid::Grid;
 use crate::input;
-use std::collections::HashMap;
 
-pub fn solve() {
-    let x = input::file_for_day(14);
+pub struct Day14;
 
-    println!("Solution part 1: {}", part_one(x.clone()));
-    println!("Solution part 2: {}", part_two(x));
+impl Solver for Day14 {
+    type Input = Vec<String>;
+
+    fn parse(raw: &str) -> Self::Input {
+        input::lines(raw)
+    }
+
+    fn part_one(input: &Self::Input) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(input: &Self::Input) -> Answer {
+        part_two(input).into()
+    }
 }
 
-fn part_one(input: Vec<String>) -> i64 {
-    drop_with_or_without_floor(&input, false)
+fn part_one(input: &[String]) -> i64 {
+    drop_with_or_without_floor(input, false)
 }
 
-fn part_two(input: Vec<String>) -> i64 {
-    drop_with_or_without_floor(&input, true)
+fn part_two(input: &[String]) -> i64 {
+    drop_with_or_without_floor(input, true)
 }
 
 fn drop_with_or_without_floor(input: &[String], with_floor: bool) -> i64 {
@@ -31,7 +44,8 @@ fn drop_with_or_without_floor(input: &[String], with_floor: bool) -> i64 {
         })
         .collect::<Vec<_>>();
 
-    let mut grid = Cave::new((480, 0), 25, with_floor);
+    let deepest = x.iter().flatten().map(|(_, y)| *y).max().unwrap();
+    let mut grid = Cave::new((480, 0), 25, with_floor, deepest);
 
     for ex in x {
         for i in 0..ex.len() - 1 {
@@ -51,7 +65,9 @@ struct Cave {
     size: i32,
     lowest_floor: i32,
     with_floor: bool,
-    inner: HashMap<(i32, i32), Item>,
+    /// The x coordinate of the first column in `inner`.
+    offset: i32,
+    inner: Grid<Item>,
 }
 
 impl std::fmt::Display for Cave {
@@ -76,19 +92,34 @@ impl std::fmt::Display for Cave {
 }
 
 impl Cave {
-    fn new(start: (i32, i32), size: i32, with_floor: bool) -> Self {
-        let mut inner = HashMap::new();
-        inner.insert((500, 0), Item::Source);
+    fn new(start: (i32, i32), size: i32, with_floor: bool, deepest_rock: i32) -> Self {
+        let lowest_floor = if with_floor {
+            deepest_rock + 2
+        } else {
+            deepest_rock
+        };
+
+        // Sand can't spread further to the sides than it can fall, so the grid only needs to be
+        // as wide as twice the depth.
+        let offset = 500 - lowest_floor - 1;
+        let width = (lowest_floor as usize + 1) * 2 + 1;
+        let mut inner = Grid::new(width, lowest_floor as usize + 1, Item::Air);
+        inner.set(Point::new(500 - offset, 0), Item::Source);
 
         Self {
             start,
             size,
             inner,
             with_floor,
-            lowest_floor: 0,
+            offset,
+            lowest_floor,
         }
     }
 
+    fn set(&mut self, (x, y): (i32, i32), item: Item) {
+        self.inner.set(Point::new(x - self.offset, y), item);
+    }
+
     fn draw_rock(&mut self, mut start: (i32, i32), stop: (i32, i32)) {
         let step = match (start, stop) {
             ((x1, _), (x2, _)) if x1 > x2 => (-1, 0),
@@ -98,25 +129,17 @@ impl Cave {
             (_, _) => unreachable!(),
         };
 
-        if stop.1 > self.lowest_floor {
-            if self.with_floor {
-                self.lowest_floor = stop.1 + 2
-            } else {
-                self.lowest_floor = stop.1
-            }
-        }
-
         while start != stop {
-            self.inner.insert(start, Item::Rock);
+            self.set(start, Item::Rock);
             start = (start.0 + step.0, start.1 + step.1);
         }
 
-        self.inner.insert(start, Item::Rock);
+        self.set(start, Item::Rock);
     }
 
     #[allow(dead_code)]
     fn draw_with_sand(&mut self, sand: (i32, i32)) {
-        self.inner.insert(sand, Item::Sand);
+        self.set(sand, Item::Sand);
         input::print_and_wait(format!("{}", self));
 
         let restore = if sand == (500, 0) {
@@ -125,14 +148,16 @@ impl Cave {
             Item::Air
         };
 
-        self.inner.insert(sand, restore);
+        self.set(sand, restore);
     }
 
     fn get(&self, pos: (i32, i32)) -> &Item {
         if self.with_floor && pos.1 == self.lowest_floor {
             &Item::Rock
         } else {
-            self.inner.get(&pos).unwrap_or(&Item::Air)
+            self.inner
+                .get(Point::new(pos.0 - self.offset, pos.1))
+                .unwrap_or(&Item::Air)
         }
     }
 
@@ -157,14 +182,14 @@ impl Cave {
             } else if self.get(down_right) == &Item::Air {
                 sand = down_right;
             } else {
-                self.inner.insert(sand, Item::Sand);
+                self.set(sand, Item::Sand);
                 return sand.1 != 0;
             }
         }
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 enum Item {
     Source,
     Rock,
@@ -198,12 +223,12 @@ mod tests {
     #[test]
     fn part_one() {
         let x = input::test_vec(TEST_INPUT);
-        assert_eq!(super::part_one(x), SOLUTION_ONE);
+        assert_eq!(super::part_one(&x), SOLUTION_ONE);
     }
 
     #[test]
     fn part_two() {
         let x = input::test_vec(TEST_INPUT);
-        assert_eq!(super::part_two(x), SOLUTION_TWO);
+        assert_eq!(super::part_two(&x), SOLUTION_TWO);
     }
 }
@@ -1,24 +1,28 @@
+use super::{Answer, Solver};
 use crate::input;
 use std::collections::HashSet;
 
 const GOAL: i64 = 2000000;
 
-pub fn solve() {
-    let x = input::file_for_day(15);
+pub struct Day15;
 
-    let p1_start = std::time::Instant::now();
-    let p1 = part_one(x.clone(), GOAL);
-    let p1_delta = p1_start.elapsed();
+impl Solver for Day15 {
+    type Input = Vec<String>;
 
-    let p2_start = std::time::Instant::now();
-    let p2 = part_two(x);
-    let p2_delta = p2_start.elapsed();
+    fn parse(raw: &str) -> Self::Input {
+        input::lines(raw)
+    }
+
+    fn part_one(input: &Self::Input) -> Answer {
+        part_one(input, GOAL).into()
+    }
 
-    println!("Solution part 1 ({:?}) {}", p1_delta, p1);
-    println!("Solution part 2 ({:?}): {}", p2_delta, p2);
+    fn part_two(input: &Self::Input) -> Answer {
+        part_two(input).into()
+    }
 }
 
-fn part_one(input: Vec<String>, goal: i64) -> i64 {
+fn part_one(input: &[String], goal: i64) -> i64 {
     let mut x = input
         .iter()
         .map(|l| {
@@ -71,7 +75,7 @@ fn part_one(input: Vec<String>, goal: i64) -> i64 {
         .fold(0, |acc, (start, end)| acc + (end - start))
 }
 
-fn part_two(input: Vec<String>) -> i128 {
+fn part_two(input: &[String]) -> i128 {
     let parsed = input
         .iter()
         .map(|l| {
@@ -175,6 +179,10 @@ fn part_two(input: Vec<String>) -> i128 {
             && candidates.contains(&two_right)
             && candidates.contains(&down_right)
             && !candidates.contains(&right)
+            && !parsed.iter().any(|(sensor, beacon)| {
+                let ((x, y), m) = xym(*sensor, *beacon);
+                (right.0 - x).abs() + (right.1 - y).abs() <= m
+            })
         {
             int = right;
             break;
@@ -246,16 +254,15 @@ Sensor at x=20, y=1: closest beacon is at x=15, y=3"#;
     #[test]
     fn part_one() {
         let x = input::test_vec(TEST_INPUT);
-        assert_eq!(super::part_one(x, TEST_GOAL), SOLUTION_ONE);
+        assert_eq!(super::part_one(&x, TEST_GOAL), SOLUTION_ONE);
     }
 
     #[test]
     fn part_two() {
-        // This test is flaky since with the smaller example there are areas with intersecting
-        // lines which is _inside_ a diamond which the current solution does not account for. With
-        // the real input the area is big enough to only give one point with 4 intersecting
-        // neighbors.
+        // With the smaller example there are areas with intersecting lines which is _inside_ a
+        // diamond so candidates covered by any sensor must be discarded. With the real input the
+        // area is big enough to only give one point with 4 intersecting neighbors.
         let x = input::test_vec(TEST_INPUT);
-        assert_eq!(super::part_two(x), SOLUTION_TWO);
+        assert_eq!(super::part_two(&x), SOLUTION_TWO);
     }
 }
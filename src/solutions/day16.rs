@@ -0,0 +1,180 @@
+use super::{Answer, Solver};
+use crate::input;
+use std::collections::{HashMap, VecDeque};
+
+pub struct Day16;
+
+impl Solver for Day16 {
+    type Input = Network;
+
+    fn parse(raw: &str) -> Self::Input {
+        Network::new(&input::lines(raw))
+    }
+
+    fn part_one(network: &Self::Input) -> Answer {
+        part_one(network).into()
+    }
+
+    fn part_two(network: &Self::Input) -> Answer {
+        part_two(network).into()
+    }
+}
+
+fn part_one(network: &Network) -> i64 {
+    let best = network.best_per_set(30);
+
+    *best.values().max().unwrap()
+}
+
+fn part_two(network: &Network) -> i64 {
+    let best = network.best_per_set(26);
+    let all = (1 << network.flows.len()) - 1;
+
+    // The best pressure for any subset of each set of valves, so that the elephant can be given
+    // the complement of whatever I open and still pick its best route within it.
+    let mut best_within = vec![0; all + 1];
+    for (&set, &pressure) in &best {
+        best_within[set] = best_within[set].max(pressure);
+    }
+
+    for bit in 0..network.flows.len() {
+        for set in 0..=all {
+            if set & (1 << bit) != 0 {
+                best_within[set] = best_within[set].max(best_within[set ^ (1 << bit)]);
+            }
+        }
+    }
+
+    best.iter()
+        .map(|(&set, &pressure)| pressure + best_within[all & !set])
+        .max()
+        .unwrap()
+}
+
+/// The valve network compressed to only the valves with a positive flow rate, with the shortest
+/// distance between each of them and from the starting valve.
+#[derive(Debug)]
+pub struct Network {
+    flows: Vec<i64>,
+    distances: Vec<Vec<i64>>,
+    start: Vec<i64>,
+}
+
+impl Network {
+    fn new(input: &[String]) -> Self {
+        let mut rates: HashMap<&str, i64> = HashMap::new();
+        let mut tunnels: HashMap<&str, Vec<&str>> = HashMap::new();
+
+        for line in input {
+            let (valve, leads_to) = line.split_once(';').unwrap();
+            let name = &valve[6..8];
+            let (_, rate) = valve.split_once('=').unwrap();
+            let leads_to = leads_to
+                .trim_start_matches(" tunnels lead to valves ")
+                .trim_start_matches(" tunnel leads to valve ");
+
+            rates.insert(name, rate.parse().unwrap());
+            tunnels.insert(name, leads_to.split(", ").collect());
+        }
+
+        let mut valves = rates
+            .iter()
+            .filter(|(_, &rate)| rate > 0)
+            .map(|(&name, _)| name)
+            .collect::<Vec<_>>();
+        valves.sort();
+
+        let distances_from = |from: &str| -> Vec<i64> {
+            let mut seen: HashMap<&str, i64> = HashMap::from([(from, 0)]);
+            let mut queue = VecDeque::from([from]);
+
+            while let Some(current) = queue.pop_front() {
+                let steps = seen[current];
+
+                for next in &tunnels[current] {
+                    if !seen.contains_key(next) {
+                        seen.insert(next, steps + 1);
+                        queue.push_back(next);
+                    }
+                }
+            }
+
+            valves.iter().map(|v| seen[v]).collect()
+        };
+
+        Self {
+            flows: valves.iter().map(|v| rates[v]).collect(),
+            distances: valves.iter().map(|v| distances_from(v)).collect(),
+            start: distances_from("AA"),
+        }
+    }
+
+    /// Walk every possible order of opening valves within `minutes`, returning the highest total
+    /// pressure released for each set of opened valves, as a bitmask over `flows`.
+    fn best_per_set(&self, minutes: i64) -> HashMap<usize, i64> {
+        let mut best = HashMap::from([(0, 0)]);
+
+        for (valve, distance) in self.start.iter().enumerate() {
+            self.visit(valve, minutes - distance - 1, 0, 0, &mut best);
+        }
+
+        best
+    }
+
+    fn visit(
+        &self,
+        valve: usize,
+        minutes_left: i64,
+        opened: usize,
+        pressure: i64,
+        best: &mut HashMap<usize, i64>,
+    ) {
+        if minutes_left <= 0 {
+            return;
+        }
+
+        let opened = opened | 1 << valve;
+        let pressure = pressure + self.flows[valve] * minutes_left;
+
+        let entry = best.entry(opened).or_insert(0);
+        *entry = (*entry).max(pressure);
+
+        for (next, distance) in self.distances[valve].iter().enumerate() {
+            if opened & 1 << next == 0 {
+                self.visit(next, minutes_left - distance - 1, opened, pressure, best);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Day16, Solver};
+    use crate::input;
+
+    static SOLUTION_ONE: i64 = 1651;
+    static SOLUTION_TWO: i64 = 1707;
+    static TEST_INPUT: &str = r#"
+Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+Valve BB has flow rate=13; tunnels lead to valves CC, AA
+Valve CC has flow rate=2; tunnels lead to valves DD, BB
+Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
+Valve EE has flow rate=3; tunnels lead to valves FF, DD
+Valve FF has flow rate=0; tunnels lead to valves EE, GG
+Valve GG has flow rate=0; tunnels lead to valves FF, HH
+Valve HH has flow rate=22; tunnel leads to valve GG
+Valve II has flow rate=0; tunnels lead to valves AA, JJ
+Valve JJ has flow rate=21; tunnel leads to valve II"#;
+
+    #[test]
+    fn part_one() {
+        let x = Day16::parse(&input::test_vec_raw(TEST_INPUT));
+        assert_eq!(super::part_one(&x), SOLUTION_ONE);
+    }
+
+    #[test]
+    fn part_two() {
+        let x = Day16::parse(&input::test_vec_raw(TEST_INPUT));
+        assert_eq!(super::part_two(&x), SOLUTION_TWO);
+    }
+}
@@ -1,39 +1,78 @@
-use crate::input;
-use std::collections::HashSet;
+use super::{Answer, Solver};
+use crate::grid::Grid;
+use std::collections::{HashMap, HashSet};
 
-pub fn solve() {
-    let x = input::raw_file_for_day(17);
+pub struct Day17;
 
-    println!("Solution part 1: {}", part_one(x.clone()));
-    println!("Solution part 2: {}", part_two(x));
+impl Solver for Day17 {
+    type Input = String;
+
+    fn parse(raw: &str) -> Self::Input {
+        raw.to_string()
+    }
+
+    fn part_one(input: &Self::Input) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(input: &Self::Input) -> Answer {
+        part_two(input).into()
+    }
+}
+
+fn part_one(input: &str) -> i64 {
+    tower_height(input, 2022)
 }
 
-fn part_one(input: String) -> i32 {
+fn part_two(input: &str) -> i64 {
+    tower_height(input, 1_000_000_000_000)
+}
+
+/// The height of the tower after `rocks` rocks have come to rest.
+///
+/// Once the next jet, the next shape and the shape of the top of the tower are the same as after
+/// some earlier rock, everything from then on repeats. When that happens we skip ahead as many
+/// whole cycles as fit, and only simulate the rocks that are left over.
+fn tower_height(input: &str, rocks: i64) -> i64 {
+    let jets = input.trim().chars().collect::<Vec<_>>();
     let mut b = Board {
         current_shape: Shape::HorizontalBar(2, 4),
-        arena: HashSet::new(),
+        arena: Grid::new(7, 1, false),
         highest_block: 0,
     };
 
-    let mut i = 0;
-    for step in input.trim().chars().cycle() {
-        b.push(step);
-        if !b.push('v') {
-            b.lock_shape();
+    let mut jet = 0;
+    let mut dropped = 0;
+    let mut seen: HashMap<(usize, usize, [i32; 7]), (i64, i32)> = HashMap::new();
+    let mut skipped = None;
+
+    while dropped < rocks {
+        loop {
+            b.push(jets[jet]);
+            jet = (jet + 1) % jets.len();
 
-            i += 1;
-            if i == 2022 {
-                // b.visualize();
+            if !b.push('v') {
+                b.lock_shape();
                 break;
             }
         }
-    }
 
-    b.highest_block
-}
+        dropped += 1;
+
+        if skipped.is_none() {
+            let state = (jet, b.shape_index(), b.profile());
+
+            if let Some((before, height)) = seen.insert(state, (dropped, b.highest_block)) {
+                let cycle = dropped - before;
+                let repeats = (rocks - dropped) / cycle;
 
-fn part_two(_input: String) -> i32 {
-    0
+                dropped += repeats * cycle;
+                skipped = Some(repeats * (b.highest_block - height) as i64);
+            }
+        }
+    }
+
+    b.highest_block as i64 + skipped.unwrap_or(0)
 }
 
 /// A shape represents a block that can have one of four different shapes:
@@ -99,11 +138,45 @@ impl Shape {
 #[derive(Debug)]
 struct Board {
     current_shape: Shape,
-    arena: HashSet<(i32, i32)>,
+    /// Resting rocks, row `y` in the grid is `y` units above the floor which is row 0.
+    arena: Grid<bool>,
     highest_block: i32,
 }
 
 impl Board {
+    fn is_rock(&self, pos: (i32, i32)) -> bool {
+        self.arena.get(pos.into()) == Some(&true)
+    }
+
+    fn place(&mut self, pos: (i32, i32)) {
+        while self.arena.height() as i32 <= pos.1 {
+            self.arena.push_row(false);
+        }
+
+        self.arena.set(pos.into(), true);
+    }
+
+    fn shape_index(&self) -> usize {
+        match self.current_shape {
+            Shape::HorizontalBar(_, _) => 0,
+            Shape::Cross(_, _) => 1,
+            Shape::Angle(_, _) => 2,
+            Shape::VerticalBar(_, _) => 3,
+            Shape::Square(_, _) => 4,
+        }
+    }
+
+    /// How far below the highest block the top rock in each column is, with the floor counting
+    /// as rock.
+    fn profile(&self) -> [i32; 7] {
+        std::array::from_fn(|x| {
+            (1..=self.highest_block)
+                .rev()
+                .find(|&y| self.is_rock((x as i32, y)))
+                .map_or(self.highest_block, |y| self.highest_block - y)
+        })
+    }
+
     fn next_shape(&mut self) {
         let gap = self.highest_block + 3;
 
@@ -141,7 +214,7 @@ impl Board {
                 return false;
             }
 
-            if self.arena.get(&(x, y)).is_some() {
+            if self.is_rock((x, y)) {
                 return false;
             }
         }
@@ -154,36 +227,36 @@ impl Board {
     fn lock_shape(&mut self) {
         match self.current_shape {
             Shape::HorizontalBar(x, y) => {
-                self.arena.insert((x, y));
-                self.arena.insert((x + 1, y));
-                self.arena.insert((x + 2, y));
-                self.arena.insert((x + 3, y));
+                self.place((x, y));
+                self.place((x + 1, y));
+                self.place((x + 2, y));
+                self.place((x + 3, y));
             }
             Shape::Cross(x, y) => {
-                self.arena.insert((x + 1, y));
-                self.arena.insert((x, y - 1));
-                self.arena.insert((x + 1, y - 1));
-                self.arena.insert((x + 2, y - 1));
-                self.arena.insert((x + 1, y - 2));
+                self.place((x + 1, y));
+                self.place((x, y - 1));
+                self.place((x + 1, y - 1));
+                self.place((x + 2, y - 1));
+                self.place((x + 1, y - 2));
             }
             Shape::Angle(x, y) => {
-                self.arena.insert((x + 2, y));
-                self.arena.insert((x + 2, y - 1));
-                self.arena.insert((x, y - 2));
-                self.arena.insert((x + 1, y - 2));
-                self.arena.insert((x + 2, y - 2));
+                self.place((x + 2, y));
+                self.place((x + 2, y - 1));
+                self.place((x, y - 2));
+                self.place((x + 1, y - 2));
+                self.place((x + 2, y - 2));
             }
             Shape::VerticalBar(x, y) => {
-                self.arena.insert((x, y));
-                self.arena.insert((x, y - 1));
-                self.arena.insert((x, y - 2));
-                self.arena.insert((x, y - 3));
+                self.place((x, y));
+                self.place((x, y - 1));
+                self.place((x, y - 2));
+                self.place((x, y - 3));
             }
             Shape::Square(x, y) => {
-                self.arena.insert((x, y));
-                self.arena.insert((x + 1, y));
-                self.arena.insert((x, y - 1));
-                self.arena.insert((x + 1, y - 1));
+                self.place((x, y));
+                self.place((x + 1, y));
+                self.place((x, y - 1));
+                self.place((x + 1, y - 1));
             }
         }
 
@@ -212,8 +285,8 @@ impl Board {
                     _ if x == -1 && y == 0 => '+',
                     _ if x == 7 && y == 0 => '+',
                     _ if y == 0 => '-',
-                    _ if shape.get(&(x, y)).is_some() => '#',
-                    _ if self.arena.get(&(x, y)).is_some() => '#',
+                    _ if shape.contains(&(x, y)) => '#',
+                    _ if self.is_rock((x, y)) => '#',
                     _ => '.',
                 };
 
@@ -229,17 +302,17 @@ impl Board {
 
 #[cfg(test)]
 mod tests {
-    static SOLUTION_ONE: i32 = 3068;
-    static SOLUTION_TWO: i32 = 0;
+    static SOLUTION_ONE: i64 = 3068;
+    static SOLUTION_TWO: i64 = 1514285714288;
     static TEST_INPUT: &str = r#">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>"#;
 
     #[test]
     fn part_one() {
-        assert_eq!(super::part_one(TEST_INPUT.to_owned()), SOLUTION_ONE);
+        assert_eq!(super::part_one(TEST_INPUT), SOLUTION_ONE);
     }
 
     #[test]
     fn part_two() {
-        assert_eq!(super::part_two(TEST_INPUT.to_owned()), SOLUTION_TWO);
+        assert_eq!(super::part_two(TEST_INPUT), SOLUTION_TWO);
     }
 }
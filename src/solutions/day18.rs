@@ -0,0 +1,124 @@
+use super::{Answer, Solver};
+use crate::input;
+use std::collections::{HashSet, VecDeque};
+
+pub struct Day18;
+
+impl Solver for Day18 {
+    type Input = HashSet<Cube>;
+
+    fn parse(raw: &str) -> Self::Input {
+        parse(&input::lines(raw))
+    }
+
+    fn part_one(cubes: &Self::Input) -> Answer {
+        part_one(cubes).into()
+    }
+
+    fn part_two(cubes: &Self::Input) -> Answer {
+        part_two(cubes).into()
+    }
+}
+
+pub type Cube = (i32, i32, i32);
+
+fn part_one(cubes: &HashSet<Cube>) -> i64 {
+    cubes
+        .iter()
+        .flat_map(neighbors)
+        .filter(|n| !cubes.contains(n))
+        .count() as i64
+}
+
+fn part_two(cubes: &HashSet<Cube>) -> i64 {
+    // Flood fill the air around the droplet within a bounding box one larger than the droplet in
+    // every direction, so the outside is connected all the way around it.
+    let (min, max) = cubes.iter().fold(
+        ((i32::MAX, i32::MAX, i32::MAX), (i32::MIN, i32::MIN, i32::MIN)),
+        |(min, max), &(x, y, z)| {
+            (
+                (min.0.min(x), min.1.min(y), min.2.min(z)),
+                (max.0.max(x), max.1.max(y), max.2.max(z)),
+            )
+        },
+    );
+    let (min, max) = (
+        (min.0 - 1, min.1 - 1, min.2 - 1),
+        (max.0 + 1, max.1 + 1, max.2 + 1),
+    );
+    let in_bounds = |&(x, y, z): &Cube| {
+        (min.0..=max.0).contains(&x) && (min.1..=max.1).contains(&y) && (min.2..=max.2).contains(&z)
+    };
+
+    let mut outside: HashSet<Cube> = HashSet::from([min]);
+    let mut queue = VecDeque::from([min]);
+    let mut faces = 0;
+
+    while let Some(air) = queue.pop_front() {
+        for n in neighbors(&air).into_iter().filter(in_bounds) {
+            if cubes.contains(&n) {
+                faces += 1;
+            } else if outside.insert(n) {
+                queue.push_back(n);
+            }
+        }
+    }
+
+    faces
+}
+
+fn parse(input: &[String]) -> HashSet<Cube> {
+    input
+        .iter()
+        .map(|line| {
+            let mut it = line.split(',').map(|n| n.parse::<i32>().unwrap());
+            (it.next().unwrap(), it.next().unwrap(), it.next().unwrap())
+        })
+        .collect()
+}
+
+fn neighbors(&(x, y, z): &Cube) -> [Cube; 6] {
+    [
+        (x + 1, y, z),
+        (x - 1, y, z),
+        (x, y + 1, z),
+        (x, y - 1, z),
+        (x, y, z + 1),
+        (x, y, z - 1),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Day18, Solver};
+    use crate::input;
+
+    static SOLUTION_ONE: i64 = 64;
+    static SOLUTION_TWO: i64 = 58;
+    static TEST_INPUT: &str = r#"
+2,2,2
+1,2,2
+3,2,2
+2,1,2
+2,3,2
+2,2,1
+2,2,3
+2,2,4
+2,2,6
+1,2,5
+3,2,5
+2,1,5
+2,3,5"#;
+
+    #[test]
+    fn part_one() {
+        let x = Day18::parse(&input::test_vec_raw(TEST_INPUT));
+        assert_eq!(super::part_one(&x), SOLUTION_ONE);
+    }
+
+    #[test]
+    fn part_two() {
+        let x = Day18::parse(&input::test_vec_raw(TEST_INPUT));
+        assert_eq!(super::part_two(&x), SOLUTION_TWO);
+    }
+}
@@ -0,0 +1,185 @@
+use super::{Answer, Solver};
+
+pub struct Day19;
+
+impl Solver for Day19 {
+    type Input = Vec<Blueprint>;
+
+    fn parse(raw: &str) -> Self::Input {
+        parse(raw)
+    }
+
+    fn part_one(blueprints: &Self::Input) -> Answer {
+        part_one(blueprints).into()
+    }
+
+    fn part_two(blueprints: &Self::Input) -> Answer {
+        part_two(blueprints).into()
+    }
+}
+
+fn part_one(blueprints: &[Blueprint]) -> i64 {
+    blueprints
+        .iter()
+        .map(|bp| bp.id * bp.max_geodes(24))
+        .sum()
+}
+
+fn part_two(blueprints: &[Blueprint]) -> i64 {
+    blueprints
+        .iter()
+        .take(3)
+        .map(|bp| bp.max_geodes(32))
+        .product()
+}
+
+const ORE: usize = 0;
+const CLAY: usize = 1;
+const OBSIDIAN: usize = 2;
+const GEODE: usize = 3;
+
+#[derive(Debug)]
+pub struct Blueprint {
+    id: i64,
+    /// The cost of each robot, indexed by the resource it collects, in ore, clay and obsidian.
+    costs: [[i64; 3]; 4],
+    /// We can only spend one robot worth of a resource per minute, so there's never any need to
+    /// have more robots collecting a resource than the most expensive recipe using it.
+    max_needed: [i64; 3],
+}
+
+impl Blueprint {
+    fn new(numbers: &[i64]) -> Self {
+        let mut costs = [[0; 3]; 4];
+        costs[ORE][ORE] = numbers[1];
+        costs[CLAY][ORE] = numbers[2];
+        costs[OBSIDIAN][ORE] = numbers[3];
+        costs[OBSIDIAN][CLAY] = numbers[4];
+        costs[GEODE][ORE] = numbers[5];
+        costs[GEODE][OBSIDIAN] = numbers[6];
+
+        let mut max_needed = [0; 3];
+        for cost in &costs {
+            for resource in ORE..=OBSIDIAN {
+                max_needed[resource] = max_needed[resource].max(cost[resource]);
+            }
+        }
+
+        Self {
+            id: numbers[0],
+            costs,
+            max_needed,
+        }
+    }
+
+    fn max_geodes(&self, minutes: i64) -> i64 {
+        let mut best = 0;
+        self.search(minutes, [1, 0, 0, 0], [0; 4], &mut best);
+
+        best
+    }
+
+    /// Depth first search over which robot to build next. Instead of stepping minute by minute we
+    /// wait until the chosen robot is affordable, which keeps the tree small.
+    fn search(&self, minutes_left: i64, robots: [i64; 4], resources: [i64; 4], best: &mut i64) {
+        let idle = resources[GEODE] + robots[GEODE] * minutes_left;
+        *best = (*best).max(idle);
+
+        // Even building a geode robot every remaining minute can't beat what we've already got.
+        if idle + minutes_left * (minutes_left - 1) / 2 <= *best {
+            return;
+        }
+
+        for robot in (ORE..=GEODE).rev() {
+            if robot != GEODE && robots[robot] >= self.max_needed[robot] {
+                continue;
+            }
+
+            let cost = &self.costs[robot];
+            let mut wait = 0;
+            let mut possible = true;
+
+            for resource in ORE..=OBSIDIAN {
+                let missing = cost[resource] - resources[resource];
+                if missing <= 0 {
+                    continue;
+                }
+
+                if robots[resource] == 0 {
+                    possible = false;
+                    break;
+                }
+
+                wait = wait.max((missing + robots[resource] - 1) / robots[resource]);
+            }
+
+            let elapsed = wait + 1;
+            if !possible || elapsed >= minutes_left {
+                continue;
+            }
+
+            let mut new_resources = resources;
+            for resource in ORE..=GEODE {
+                new_resources[resource] += robots[resource] * elapsed;
+            }
+
+            for resource in ORE..=OBSIDIAN {
+                new_resources[resource] -= cost[resource];
+            }
+
+            let mut new_robots = robots;
+            new_robots[robot] += 1;
+
+            self.search(minutes_left - elapsed, new_robots, new_resources, best);
+        }
+    }
+}
+
+fn parse(input: &str) -> Vec<Blueprint> {
+    input
+        .split("Blueprint")
+        .filter(|bp| !bp.trim().is_empty())
+        .map(|bp| {
+            let numbers = bp
+                .split(|c: char| !c.is_ascii_digit())
+                .filter(|n| !n.is_empty())
+                .map(|n| n.parse::<i64>().unwrap())
+                .collect::<Vec<_>>();
+
+            Blueprint::new(&numbers)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Day19, Solver};
+    use crate::input;
+
+    static SOLUTION_ONE: i64 = 33;
+    static SOLUTION_TWO: i64 = 56 * 62;
+    static TEST_INPUT: &str = r#"
+Blueprint 1:
+  Each ore robot costs 4 ore.
+  Each clay robot costs 2 ore.
+  Each obsidian robot costs 3 ore and 14 clay.
+  Each geode robot costs 2 ore and 7 obsidian.
+
+Blueprint 2:
+  Each ore robot costs 2 ore.
+  Each clay robot costs 3 ore.
+  Each obsidian robot costs 3 ore and 8 clay.
+  Each geode robot costs 3 ore and 12 obsidian."#;
+
+    #[test]
+    fn part_one() {
+        let x = Day19::parse(&input::test_vec_raw(TEST_INPUT));
+        assert_eq!(super::part_one(&x), SOLUTION_ONE);
+    }
+
+    #[test]
+    fn part_two() {
+        let x = Day19::parse(&input::test_vec_raw(TEST_INPUT));
+        assert_eq!(super::part_two(&x), SOLUTION_TWO);
+    }
+}
@@ -0,0 +1,97 @@
+use super::{Answer, Solver};
+use crate::input;
+
+pub struct Day20;
+
+impl Solver for Day20 {
+    type Input = Vec<i64>;
+
+    fn parse(raw: &str) -> Self::Input {
+        parse(&input::lines(raw))
+    }
+
+    fn part_one(numbers: &Self::Input) -> Answer {
+        part_one(numbers).into()
+    }
+
+    fn part_two(numbers: &Self::Input) -> Answer {
+        part_two(numbers).into()
+    }
+}
+
+const DECRYPTION_KEY: i64 = 811589153;
+
+fn part_one(numbers: &[i64]) -> i64 {
+    grove_coordinates(&mix(numbers, 1))
+}
+
+fn part_two(numbers: &[i64]) -> i64 {
+    let numbers = numbers
+        .iter()
+        .map(|n| n * DECRYPTION_KEY)
+        .collect::<Vec<_>>();
+
+    grove_coordinates(&mix(&numbers, 10))
+}
+
+/// Mix the numbers `rounds` times, always moving them in their original order. Instead of moving
+/// the numbers themselves we shuffle their original indices, so duplicates are told apart.
+fn mix(numbers: &[i64], rounds: usize) -> Vec<i64> {
+    let mut order = (0..numbers.len()).collect::<Vec<_>>();
+    // Moving a number around the circle skips over the other len - 1 numbers only.
+    let others = numbers.len() as i64 - 1;
+
+    for _ in 0..rounds {
+        for (original, &n) in numbers.iter().enumerate() {
+            let from = order.iter().position(|&i| i == original).unwrap();
+            order.remove(from);
+
+            let to = (from as i64 + n).rem_euclid(others) as usize;
+            order.insert(to, original);
+        }
+    }
+
+    order.into_iter().map(|i| numbers[i]).collect()
+}
+
+fn grove_coordinates(mixed: &[i64]) -> i64 {
+    let zero = mixed.iter().position(|&n| n == 0).unwrap();
+
+    [1000, 2000, 3000]
+        .iter()
+        .map(|offset| mixed[(zero + offset) % mixed.len()])
+        .sum()
+}
+
+fn parse(input: &[String]) -> Vec<i64> {
+    input.iter().map(|line| line.parse().unwrap()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Day20, Solver};
+    use crate::input;
+
+    static SOLUTION_ONE: i64 = 3;
+    static SOLUTION_TWO: i64 = 1623178306;
+    static TEST_INPUT: &str = r#"
+1
+2
+-3
+3
+-2
+0
+4"#;
+
+    #[test]
+    fn part_one() {
+        let x = Day20::parse(&input::test_vec_raw(TEST_INPUT));
+        assert_eq!(super::part_one(&x), SOLUTION_ONE);
+    }
+
+    #[test]
+    fn part_two() {
+        let x = Day20::parse(&input::test_vec_raw(TEST_INPUT));
+        assert_eq!(super::part_two(&x), SOLUTION_TWO);
+    }
+}
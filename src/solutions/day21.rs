@@ -0,0 +1,176 @@
+use super::{Answer, Solver};
+use crate::input;
+use std::collections::HashMap;
+
+pub struct Day21;
+
+impl Solver for Day21 {
+    type Input = HashMap<String, Job>;
+
+    fn parse(raw: &str) -> Self::Input {
+        parse(&input::lines(raw))
+    }
+
+    fn part_one(monkeys: &Self::Input) -> Answer {
+        part_one(monkeys).into()
+    }
+
+    fn part_two(monkeys: &Self::Input) -> Answer {
+        part_two(monkeys).into()
+    }
+}
+
+const ROOT: &str = "root";
+const HUMAN: &str = "humn";
+
+fn part_one(monkeys: &HashMap<String, Job>) -> i64 {
+    evaluate(monkeys, ROOT)
+}
+
+fn part_two(monkeys: &HashMap<String, Job>) -> i64 {
+    let Job::Operation(left, _, right) = &monkeys[ROOT] else {
+        panic!("root must wait for two other monkeys");
+    };
+
+    // Only one side of root depends on the human, so the other one is the value it must match.
+    // From there we walk down towards the human undoing each operation on the way.
+    let (mut name, mut target) = if depends_on_human(monkeys, left) {
+        (left.as_str(), evaluate(monkeys, right))
+    } else {
+        (right.as_str(), evaluate(monkeys, left))
+    };
+
+    while name != HUMAN {
+        let Job::Operation(left, op, right) = &monkeys[name] else {
+            unreachable!("only operations can depend on the human");
+        };
+
+        if depends_on_human(monkeys, left) {
+            let known = evaluate(monkeys, right);
+            target = match op {
+                Operator::Add => target - known,
+                Operator::Sub => target + known,
+                Operator::Mul => target / known,
+                Operator::Div => target * known,
+            };
+            name = left;
+        } else {
+            let known = evaluate(monkeys, left);
+            target = match op {
+                Operator::Add => target - known,
+                Operator::Sub => known - target,
+                Operator::Mul => target / known,
+                Operator::Div => known / target,
+            };
+            name = right;
+        }
+    }
+
+    target
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Operator {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+impl Operator {
+    fn apply(&self, a: i64, b: i64) -> i64 {
+        match self {
+            Operator::Add => a + b,
+            Operator::Sub => a - b,
+            Operator::Mul => a * b,
+            Operator::Div => a / b,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Job {
+    Number(i64),
+    Operation(String, Operator, String),
+}
+
+fn evaluate(monkeys: &HashMap<String, Job>, name: &str) -> i64 {
+    match &monkeys[name] {
+        Job::Number(n) => *n,
+        Job::Operation(left, op, right) => {
+            op.apply(evaluate(monkeys, left), evaluate(monkeys, right))
+        }
+    }
+}
+
+fn depends_on_human(monkeys: &HashMap<String, Job>, name: &str) -> bool {
+    name == HUMAN
+        || match &monkeys[name] {
+            Job::Number(_) => false,
+            Job::Operation(left, _, right) => {
+                depends_on_human(monkeys, left) || depends_on_human(monkeys, right)
+            }
+        }
+}
+
+fn parse(input: &[String]) -> HashMap<String, Job> {
+    input
+        .iter()
+        .map(|line| {
+            let (name, job) = line.split_once(": ").unwrap();
+            let job = match job.split(' ').collect::<Vec<_>>()[..] {
+                [n] => Job::Number(n.parse().unwrap()),
+                [left, op, right] => {
+                    let op = match op {
+                        "+" => Operator::Add,
+                        "-" => Operator::Sub,
+                        "*" => Operator::Mul,
+                        "/" => Operator::Div,
+                        _ => unreachable!(),
+                    };
+                    Job::Operation(left.to_string(), op, right.to_string())
+                }
+                _ => unreachable!(),
+            };
+
+            (name.to_string(), job)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Day21, Solver};
+    use crate::input;
+
+    static SOLUTION_ONE: i64 = 152;
+    static SOLUTION_TWO: i64 = 301;
+    static TEST_INPUT: &str = r#"
+root: pppw + sjmn
+dbpl: 5
+cczh: sllz + lgvd
+zczc: 2
+ptdq: humn - dvpt
+dvpt: 3
+lfqf: 4
+humn: 5
+ljgn: 2
+sjmn: drzm * dbpl
+sllz: 4
+pppw: cczh / lfqf
+lgvd: ljgn * ptdq
+drzm: hmdt - zczc
+hmdt: 32"#;
+
+    #[test]
+    fn part_one() {
+        let x = Day21::parse(&input::test_vec_raw(TEST_INPUT));
+        assert_eq!(super::part_one(&x), SOLUTION_ONE);
+    }
+
+    #[test]
+    fn part_two() {
+        let x = Day21::parse(&input::test_vec_raw(TEST_INPUT));
+        assert_eq!(super::part_two(&x), SOLUTION_TWO);
+    }
+}
@@ -0,0 +1,306 @@
+use super::{Answer, Solver};
+use crate::geometry::{Direction, Point};
+use crate::grid::Grid;
+use crate::input;
+use std::collections::{HashMap, VecDeque};
+
+pub struct Day22;
+
+impl Solver for Day22 {
+    type Input = Board;
+
+    fn parse(raw: &str) -> Self::Input {
+        Board::new(&input::lines(raw))
+    }
+
+    fn part_one(board: &Self::Input) -> Answer {
+        part_one(board).into()
+    }
+
+    fn part_two(board: &Self::Input) -> Answer {
+        part_two(board).into()
+    }
+}
+
+fn part_one(board: &Board) -> i64 {
+    board.walk(|pos, dir| board.wrap_flat(pos, dir))
+}
+
+fn part_two(board: &Board) -> i64 {
+    let cube = Cube::fold(board);
+
+    board.walk(|pos, dir| cube.wrap(pos, dir))
+}
+
+#[derive(Debug)]
+enum Instruction {
+    Forward(usize),
+    Left,
+    Right,
+}
+
+#[derive(Debug)]
+pub struct Board {
+    /// The map padded with spaces to a rectangle, so anything off the map is a space.
+    map: Grid<char>,
+    path: Vec<Instruction>,
+}
+
+impl Board {
+    fn new(input: &[String]) -> Self {
+        let mut lines = input.iter().filter(|l| !l.is_empty()).collect::<Vec<_>>();
+        let path = lines.pop().unwrap();
+
+        let width = lines.iter().map(|l| l.len()).max().unwrap();
+        let lines = lines
+            .iter()
+            .map(|l| format!("{:width$}", l))
+            .collect::<Vec<_>>();
+
+        let mut instructions = Vec::new();
+        let mut steps = String::new();
+        for c in path.chars() {
+            if c.is_ascii_digit() {
+                steps.push(c);
+                continue;
+            }
+
+            if !steps.is_empty() {
+                instructions.push(Instruction::Forward(steps.parse().unwrap()));
+                steps.clear();
+            }
+
+            instructions.push(match c {
+                'L' => Instruction::Left,
+                'R' => Instruction::Right,
+                _ => unreachable!(),
+            });
+        }
+
+        if !steps.is_empty() {
+            instructions.push(Instruction::Forward(steps.parse().unwrap()));
+        }
+
+        Self {
+            map: Grid::from_lines(&lines, |c| c),
+            path: instructions,
+        }
+    }
+
+    fn tile(&self, pos: Point) -> char {
+        self.map.get(pos).copied().unwrap_or(' ')
+    }
+
+    /// Follow the path from the leftmost open tile of the top row and return the password for
+    /// where we end up. `wrap` gives the position and direction after stepping off the map.
+    fn walk<W>(&self, wrap: W) -> i64
+    where
+        W: Fn(Point, Direction) -> (Point, Direction),
+    {
+        let mut pos = self.map.position(|&c| c == '.').unwrap();
+        let mut dir = Direction::Right;
+
+        for instruction in &self.path {
+            match instruction {
+                Instruction::Left => dir = dir.turn_left(),
+                Instruction::Right => dir = dir.turn_right(),
+                Instruction::Forward(steps) => {
+                    for _ in 0..*steps {
+                        let mut next = (pos + dir.step(), dir);
+                        if self.tile(next.0) == ' ' {
+                            next = wrap(pos, dir);
+                        }
+
+                        if self.tile(next.0) == '#' {
+                            break;
+                        }
+
+                        (pos, dir) = next;
+                    }
+                }
+            }
+        }
+
+        let facing = match dir {
+            Direction::Right => 0,
+            Direction::Down => 1,
+            Direction::Left => 2,
+            Direction::Up => 3,
+        };
+
+        1000 * (pos.y as i64 + 1) + 4 * (pos.x as i64 + 1) + facing
+    }
+
+    /// Wrap around to the other side of the row or column we're walking along.
+    fn wrap_flat(&self, pos: Point, dir: Direction) -> (Point, Direction) {
+        let mut pos = pos;
+        while self.tile(pos - dir.step()) != ' ' {
+            pos -= dir.step();
+        }
+
+        (pos, dir)
+    }
+}
+
+type Vector = [i32; 3];
+
+fn neg(v: Vector) -> Vector {
+    v.map(|c| -c)
+}
+
+/// How a face of the map sits on the cube, as the outward normal and the directions that right
+/// and down on the map point to in three dimensions.
+#[derive(Debug, Clone, Copy)]
+struct Orientation {
+    normal: Vector,
+    right: Vector,
+    down: Vector,
+}
+
+impl Orientation {
+    fn vector(&self, dir: Direction) -> Vector {
+        match dir {
+            Direction::Right => self.right,
+            Direction::Left => neg(self.right),
+            Direction::Down => self.down,
+            Direction::Up => neg(self.down),
+        }
+    }
+
+    /// The orientation of the face next to this one on the map in direction `dir`, once the
+    /// cube is folded. Walking over the edge we end up facing away from this face's normal, while
+    /// the axis along the edge stays the same.
+    fn fold(&self, dir: Direction) -> Self {
+        let mut next = *self;
+        next.normal = self.vector(dir);
+
+        match dir {
+            Direction::Right => next.right = neg(self.normal),
+            Direction::Left => next.right = self.normal,
+            Direction::Down => next.down = neg(self.normal),
+            Direction::Up => next.down = self.normal,
+        }
+
+        next
+    }
+}
+
+/// The map folded into a cube. Faces are keyed by their position on the map in units of `size`.
+#[derive(Debug)]
+struct Cube {
+    size: i32,
+    faces: HashMap<Point, Orientation>,
+}
+
+impl Cube {
+    fn fold(board: &Board) -> Self {
+        let area = board.map.iter().filter(|(_, &c)| c != ' ').count();
+        let size = (area / 6).isqrt() as i32;
+
+        let first = board.map.position(|&c| c != ' ').unwrap();
+        let first = Point::new(first.x / size, first.y / size);
+        let mut faces = HashMap::from([(
+            first,
+            Orientation {
+                normal: [0, 0, 1],
+                right: [1, 0, 0],
+                down: [0, 1, 0],
+            },
+        )]);
+        let mut queue = VecDeque::from([first]);
+
+        while let Some(face) = queue.pop_front() {
+            let orientation = faces[&face];
+
+            for dir in Direction::ALL {
+                let next = face + dir.step();
+                if board.tile(next * size) != ' ' && !faces.contains_key(&next) {
+                    faces.insert(next, orientation.fold(dir));
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        Self { size, faces }
+    }
+
+    /// Step over the edge of the face `pos` is on and onto the face that's folded against it.
+    fn wrap(&self, pos: Point, dir: Direction) -> (Point, Direction) {
+        let face = Point::new(pos.x / self.size, pos.y / self.size);
+        let from = self.faces[&face];
+        let local = pos - face * self.size;
+
+        let heading = from.vector(dir);
+        let (&to_face, to) = self
+            .faces
+            .iter()
+            .find(|(_, o)| o.normal == heading)
+            .unwrap();
+        let new_dir = Direction::ALL
+            .into_iter()
+            .find(|&d| to.vector(d) == neg(from.normal))
+            .unwrap();
+
+        // How far along the shared edge we are, which might be counted from the other end once
+        // we're on the new face.
+        let (offset, along) = match dir {
+            Direction::Left | Direction::Right => (local.y, from.down),
+            Direction::Up | Direction::Down => (local.x, from.right),
+        };
+        let to_along = match new_dir {
+            Direction::Left | Direction::Right => to.down,
+            Direction::Up | Direction::Down => to.right,
+        };
+        let offset = if to_along == along {
+            offset
+        } else {
+            self.size - 1 - offset
+        };
+
+        let last = self.size - 1;
+        let local = match new_dir {
+            Direction::Right => Point::new(0, offset),
+            Direction::Left => Point::new(last, offset),
+            Direction::Down => Point::new(offset, 0),
+            Direction::Up => Point::new(offset, last),
+        };
+
+        (to_face * self.size + local, new_dir)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Day22, Solver};
+    use crate::input;
+
+    static SOLUTION_ONE: i64 = 6032;
+    static SOLUTION_TWO: i64 = 5031;
+    static TEST_INPUT: &str = r#"
+        ...#
+        .#..
+        #...
+        ....
+...#.......#
+........#...
+..#....#....
+..........#.
+        ...#....
+        .....#..
+        .#......
+        ......#.
+
+10R5L5R10L4R5L5"#;
+
+    #[test]
+    fn part_one() {
+        let x = Day22::parse(&input::test_vec_raw(TEST_INPUT));
+        assert_eq!(super::part_one(&x), SOLUTION_ONE);
+    }
+
+    #[test]
+    fn part_two() {
+        let x = Day22::parse(&input::test_vec_raw(TEST_INPUT));
+        assert_eq!(super::part_two(&x), SOLUTION_TWO);
+    }
+}
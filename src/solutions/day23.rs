@@ -0,0 +1,137 @@
+use super::{Answer, Solver};
+use crate::geometry::{Direction, Point};
+use crate::input;
+use std::collections::{HashMap, HashSet};
+
+pub struct Day23;
+
+impl Solver for Day23 {
+    type Input = HashSet<Point>;
+
+    fn parse(raw: &str) -> Self::Input {
+        parse(&input::lines(raw))
+    }
+
+    fn part_one(elves: &Self::Input) -> Answer {
+        part_one(elves).into()
+    }
+
+    fn part_two(elves: &Self::Input) -> Answer {
+        part_two(elves).into()
+    }
+}
+
+fn part_one(elves: &HashSet<Point>) -> i64 {
+    let mut elves = elves.clone();
+
+    for round in 0..10 {
+        spread(&mut elves, round);
+    }
+
+    let (min, max) = elves.iter().fold(
+        (Point::new(i32::MAX, i32::MAX), Point::new(i32::MIN, i32::MIN)),
+        |(min, max), p| {
+            (
+                Point::new(min.x.min(p.x), min.y.min(p.y)),
+                Point::new(max.x.max(p.x), max.y.max(p.y)),
+            )
+        },
+    );
+
+    ((max.x - min.x + 1) * (max.y - min.y + 1)) as i64 - elves.len() as i64
+}
+
+fn part_two(elves: &HashSet<Point>) -> i64 {
+    let mut elves = elves.clone();
+
+    (0..).find(|&round| !spread(&mut elves, round)).unwrap() as i64 + 1
+}
+
+/// The order the elves consider moving in, the first direction rotating to the back each round.
+const ORDER: [Direction; 4] = [
+    Direction::Up,
+    Direction::Down,
+    Direction::Left,
+    Direction::Right,
+];
+
+/// Run a single round, returning whether any elf moved.
+fn spread(elves: &mut HashSet<Point>, round: usize) -> bool {
+    // Proposed destination for each elf that wants to move, and how many want to go there.
+    let mut proposals: HashMap<Point, Point> = HashMap::new();
+    let mut wanted: HashMap<Point, usize> = HashMap::new();
+
+    for &elf in elves.iter() {
+        if elf.neighbors8().iter().all(|n| !elves.contains(n)) {
+            continue;
+        }
+
+        let free = |dir: Direction| {
+            // The tile in that direction and the two diagonals next to it.
+            let ahead = elf + dir.step();
+            let side = dir.turn_right().step();
+            [ahead - side, ahead, ahead + side]
+                .iter()
+                .all(|p| !elves.contains(p))
+        };
+
+        if let Some(dir) = (0..4).map(|i| ORDER[(round + i) % 4]).find(|&d| free(d)) {
+            let target = elf + dir.step();
+            proposals.insert(elf, target);
+            *wanted.entry(target).or_insert(0) += 1;
+        }
+    }
+
+    let mut moved = false;
+    for (elf, target) in proposals {
+        if wanted[&target] == 1 {
+            elves.remove(&elf);
+            elves.insert(target);
+            moved = true;
+        }
+    }
+
+    moved
+}
+
+fn parse(input: &[String]) -> HashSet<Point> {
+    input
+        .iter()
+        .enumerate()
+        .flat_map(|(y, line)| {
+            line.chars()
+                .enumerate()
+                .filter(|&(_, c)| c == '#')
+                .map(move |(x, _)| Point::new(x as i32, y as i32))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Day23, Solver};
+    use crate::input;
+
+    static SOLUTION_ONE: i64 = 110;
+    static SOLUTION_TWO: i64 = 20;
+    static TEST_INPUT: &str = r#"
+....#..
+..###.#
+#...#.#
+.#...##
+#.###..
+##.#.##
+.#..#.."#;
+
+    #[test]
+    fn part_one() {
+        let x = Day23::parse(&input::test_vec_raw(TEST_INPUT));
+        assert_eq!(super::part_one(&x), SOLUTION_ONE);
+    }
+
+    #[test]
+    fn part_two() {
+        let x = Day23::parse(&input::test_vec_raw(TEST_INPUT));
+        assert_eq!(super::part_two(&x), SOLUTION_TWO);
+    }
+}
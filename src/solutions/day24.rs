@@ -0,0 +1,153 @@
+use super::{Answer, Solver};
+use crate::geometry::Point;
+use crate::grid::Grid;
+use crate::input;
+use pathfinding::prelude::bfs;
+
+pub struct Day24;
+
+impl Solver for Day24 {
+    type Input = Valley;
+
+    fn parse(raw: &str) -> Self::Input {
+        Valley::new(&input::lines(raw))
+    }
+
+    fn part_one(valley: &Self::Input) -> Answer {
+        part_one(valley).into()
+    }
+
+    fn part_two(valley: &Self::Input) -> Answer {
+        part_two(valley).into()
+    }
+}
+
+fn part_one(valley: &Valley) -> i64 {
+    valley.crossing(valley.start, valley.end, 0) as i64
+}
+
+fn part_two(valley: &Valley) -> i64 {
+    let there = valley.crossing(valley.start, valley.end, 0);
+    let back = there + valley.crossing(valley.end, valley.start, there);
+
+    (back + valley.crossing(valley.start, valley.end, back)) as i64
+}
+
+/// The inside of the valley without its walls, so the entrance sits just above the top row and
+/// the exit just below the bottom row.
+#[derive(Debug)]
+pub struct Valley {
+    blizzards: Grid<char>,
+    start: Point,
+    end: Point,
+    /// Every blizzard is back where it started after this many minutes.
+    period: usize,
+}
+
+impl Valley {
+    fn new(input: &[String]) -> Self {
+        let inner = input[1..input.len() - 1]
+            .iter()
+            .map(|line| &line[1..line.len() - 1])
+            .collect::<Vec<_>>();
+        let blizzards = Grid::from_lines(&inner, |c| c);
+
+        let opening = |line: &str| line.find('.').unwrap() as i32 - 1;
+        let start = Point::new(opening(&input[0]), -1);
+        let end = Point::new(
+            opening(&input[input.len() - 1]),
+            blizzards.height() as i32,
+        );
+
+        let (width, height) = (blizzards.width(), blizzards.height());
+        let period = width / gcd(width, height) * height;
+
+        Self {
+            blizzards,
+            start,
+            end,
+            period,
+        }
+    }
+
+    /// Whether there's no wall and no blizzard at `pos` after `minute` minutes. Instead of moving
+    /// the blizzards we look back to where a blizzard would have had to start to be here now.
+    fn is_free(&self, pos: Point, minute: usize) -> bool {
+        if pos == self.start || pos == self.end {
+            return true;
+        }
+
+        if !self.blizzards.contains(pos) {
+            return false;
+        }
+
+        let (width, height) = (
+            self.blizzards.width() as i32,
+            self.blizzards.height() as i32,
+        );
+        let t = minute as i32;
+        let at = |x: i32, y: i32| self.blizzards.get(Point::new(x, y)).copied();
+
+        at((pos.x - t).rem_euclid(width), pos.y) != Some('>')
+            && at((pos.x + t).rem_euclid(width), pos.y) != Some('<')
+            && at(pos.x, (pos.y - t).rem_euclid(height)) != Some('v')
+            && at(pos.x, (pos.y + t).rem_euclid(height)) != Some('^')
+    }
+
+    /// The fewest minutes to get from `from` to `to` when setting off at minute `departure`.
+    fn crossing(&self, from: Point, to: Point, departure: usize) -> usize {
+        let path = bfs(
+            &(from, departure % self.period),
+            |&(pos, minute)| {
+                let minute = (minute + 1) % self.period;
+
+                pos.neighbors4()
+                    .into_iter()
+                    .chain(std::iter::once(pos))
+                    .filter(|&p| self.is_free(p, minute))
+                    .map(|p| (p, minute))
+                    .collect::<Vec<_>>()
+            },
+            |&(pos, _)| pos == to,
+        )
+        .expect("no way through the valley");
+
+        path.len() - 1
+    }
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Day24, Solver};
+    use crate::input;
+
+    static SOLUTION_ONE: i64 = 18;
+    static SOLUTION_TWO: i64 = 54;
+    static TEST_INPUT: &str = r#"
+#.######
+#>>.<^<#
+#.<..<<#
+#>v.><>#
+#<^v^^>#
+######.#"#;
+
+    #[test]
+    fn part_one() {
+        let x = Day24::parse(&input::test_vec_raw(TEST_INPUT));
+        assert_eq!(super::part_one(&x), SOLUTION_ONE);
+    }
+
+    #[test]
+    fn part_two() {
+        let x = Day24::parse(&input::test_vec_raw(TEST_INPUT));
+        assert_eq!(super::part_two(&x), SOLUTION_TWO);
+    }
+}
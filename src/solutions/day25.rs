@@ -0,0 +1,240 @@
+use super::{Answer, Solver};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub};
+
+pub struct Day25;
+
+impl Solver for Day25 {
+    type Input = Vec<Snafu>;
+
+    fn parse(raw: &str) -> Self::Input {
+        raw.lines()
+            .map(|line| {
+                line.parse()
+                    .unwrap_or_else(|e| panic!("invalid SNAFU number `{}`: {}", line, e))
+            })
+            .collect()
+    }
+
+    fn part_one(numbers: &Self::Input) -> Answer {
+        part_one(numbers).into()
+    }
+
+    fn part_two(numbers: &Self::Input) -> Answer {
+        part_two(numbers).into()
+    }
+}
+
+fn part_one(numbers: &[Snafu]) -> String {
+    numbers.iter().copied().sum::<Snafu>().to_string()
+}
+
+/// There's no puzzle for the second part of the last day, just the fifty stars.
+fn part_two(_numbers: &[Snafu]) -> String {
+    "Merry Christmas!".to_string()
+}
+
+/// A number written in balanced base five, where the digits `=`, `-`, `0`, `1` and `2` stand for
+/// -2 to 2. Negative numbers need no sign, their leading digit is simply negative.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Snafu(i64);
+
+impl Snafu {
+    fn digit(c: char) -> Option<i64> {
+        match c {
+            '=' => Some(-2),
+            '-' => Some(-1),
+            '0' => Some(0),
+            '1' => Some(1),
+            '2' => Some(2),
+            _ => None,
+        }
+    }
+}
+
+impl From<i64> for Snafu {
+    fn from(n: i64) -> Self {
+        Snafu(n)
+    }
+}
+
+impl From<Snafu> for i64 {
+    fn from(n: Snafu) -> Self {
+        n.0
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ParseSnafuError {
+    Empty,
+    InvalidDigit(char),
+}
+
+impl std::fmt::Display for ParseSnafuError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ParseSnafuError::Empty => write!(f, "cannot parse a SNAFU number from an empty string"),
+            ParseSnafuError::InvalidDigit(c) => write!(f, "invalid SNAFU digit `{}`", c),
+        }
+    }
+}
+
+impl std::str::FromStr for Snafu {
+    type Err = ParseSnafuError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(ParseSnafuError::Empty);
+        }
+
+        s.chars()
+            .try_fold(0, |n, c| {
+                Snafu::digit(c)
+                    .map(|d| n * 5 + d)
+                    .ok_or(ParseSnafuError::InvalidDigit(c))
+            })
+            .map(Snafu)
+    }
+}
+
+impl std::fmt::Display for Snafu {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if self.0 == 0 {
+            return f.pad("0");
+        }
+
+        let mut n = self.0;
+        let mut digits = Vec::new();
+
+        while n != 0 {
+            let digit = match n.rem_euclid(5) {
+                r @ 0..=2 => r,
+                r => r - 5,
+            };
+            digits.push(match digit {
+                -2 => '=',
+                -1 => '-',
+                0 => '0',
+                1 => '1',
+                _ => '2',
+            });
+            n = (n - digit) / 5;
+        }
+
+        f.pad(&digits.iter().rev().collect::<String>())
+    }
+}
+
+impl Add for Snafu {
+    type Output = Snafu;
+
+    fn add(self, other: Snafu) -> Snafu {
+        Snafu(self.0 + other.0)
+    }
+}
+
+impl Sub for Snafu {
+    type Output = Snafu;
+
+    fn sub(self, other: Snafu) -> Snafu {
+        Snafu(self.0 - other.0)
+    }
+}
+
+impl Mul for Snafu {
+    type Output = Snafu;
+
+    fn mul(self, other: Snafu) -> Snafu {
+        Snafu(self.0 * other.0)
+    }
+}
+
+impl Neg for Snafu {
+    type Output = Snafu;
+
+    fn neg(self) -> Snafu {
+        Snafu(-self.0)
+    }
+}
+
+impl AddAssign for Snafu {
+    fn add_assign(&mut self, other: Snafu) {
+        *self = *self + other;
+    }
+}
+
+impl std::iter::Sum for Snafu {
+    fn sum<I: Iterator<Item = Snafu>>(iter: I) -> Snafu {
+        iter.fold(Snafu::default(), Add::add)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Day25, ParseSnafuError, Snafu, Solver};
+    use crate::input;
+
+    static SOLUTION_ONE: &str = "2=-1=0";
+    static TEST_INPUT: &str = r#"
+1=-0-2
+12111
+2=0=
+21
+2=01
+111
+20012
+112
+1=-1=
+1-12
+12
+1=
+122"#;
+
+    #[test]
+    fn part_one() {
+        let x = Day25::parse(&input::test_vec_raw(TEST_INPUT));
+        assert_eq!(super::part_one(&x), SOLUTION_ONE);
+    }
+
+    #[test]
+    fn conversion() {
+        let table = [
+            (1, "1"),
+            (2, "2"),
+            (3, "1="),
+            (4, "1-"),
+            (5, "10"),
+            (6, "11"),
+            (7, "12"),
+            (8, "2="),
+            (9, "2-"),
+            (10, "20"),
+            (15, "1=0"),
+            (20, "1-0"),
+            (2022, "1=11-2"),
+            (12345, "1-0---0"),
+            (314159265, "1121-1110-1=0"),
+        ];
+
+        for (decimal, snafu) in table {
+            assert_eq!(Snafu::from(decimal).to_string(), snafu);
+            assert_eq!(snafu.parse::<Snafu>().map(i64::from), Ok(decimal));
+        }
+
+        assert_eq!(Snafu::from(0).to_string(), "0");
+        assert_eq!(Snafu::from(-3).to_string(), "-2");
+        assert_eq!("-2".parse(), Ok(Snafu::from(-3)));
+        assert_eq!("".parse::<Snafu>(), Err(ParseSnafuError::Empty));
+        assert_eq!("13".parse::<Snafu>(), Err(ParseSnafuError::InvalidDigit('3')));
+    }
+
+    #[test]
+    fn arithmetic() {
+        let (a, b) = (Snafu::from(2022), Snafu::from(8));
+
+        assert_eq!((a + b).to_string(), "1=1110");
+        assert_eq!(i64::from(a - b), 2014);
+        assert_eq!(i64::from(a * b), 16176);
+        assert_eq!(-a + a, Snafu::default());
+        assert_eq!([a, b].into_iter().sum::<Snafu>(), Snafu::from(2030));
+    }
+}
@@ -1,49 +1,145 @@
-mod day01;
-mod day02;
-mod day03;
-mod day04;
-mod day05;
-mod day06;
-mod day07;
-mod day08;
-mod day09;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day17;
-
-pub fn solution_for(day: i32) {
-    println!("Showing solution for day {}\n", day);
-
-    match day {
-        1 => day01::solve(),
-        2 => day02::solve(),
-        3 => day03::solve(),
-        4 => day04::solve(),
-        5 => day05::solve(),
-        6 => day06::solve(),
-        7 => day07::solve(),
-        8 => day08::solve(),
-        9 => day09::solve(),
-        10 => day10::solve(),
-        11 => day11::solve(),
-        12 => day12::solve(),
-        13 => day13::solve(),
-        14 => day14::solve(),
-        15 => day15::solve(),
-        16 => todo!(),
-        17 => day17::solve(),
-        18 => todo!(),
-        19 => todo!(),
-        20 => todo!(),
-        21 => todo!(),
-        22 => todo!(),
-        23 => todo!(),
-        24 => todo!(),
-        25 => todo!(),
-        _ => panic!("Invalid day"),
+use std::marker::PhantomData;
+
+mod answer;
+
+pub use answer::Answer;
+
+/// Every day registers its solver here. Adding a new day is a matter of adding a line to this
+/// list, the module declaration and registry entry is generated by the macro.
+macro_rules! days {
+    ($($day:literal => $module:ident::$solver:ident),* $(,)?) => {
+        $(mod $module;)*
+
+        /// All implemented days, ordered by day number.
+        pub fn registry() -> Vec<Day> {
+            let mut days = vec![$(Day::new::<$module::$solver>($day)),*];
+            days.sort_by_key(|d| d.day);
+
+            days
+        }
+    };
+}
+
+days! {
+    1 => day01::Day01,
+    2 => day02::Day02,
+    3 => day03::Day03,
+    4 => day04::Day04,
+    5 => day05::Day05,
+    6 => day06::Day06,
+    7 => day07::Day07,
+    8 => day08::Day08,
+    9 => day09::Day09,
+    10 => day10::Day10,
+    11 => day11::Day11,
+    12 => day12::Day12,
+    13 => day13::Day13,
+    14 => day14::Day14,
+    15 => day15::Day15,
+    16 => day16::Day16,
+    17 => day17::Day17,
+    18 => day18::Day18,
+    19 => day19::Day19,
+    20 => day20::Day20,
+    21 => day21::Day21,
+    22 => day22::Day22,
+    23 => day23::Day23,
+    24 => day24::Day24,
+    25 => day25::Day25,
+}
+
+/// A solver for a single day. The puzzle input is parsed once with `parse` and the result is
+/// then passed to each part so they can be run independently. `Input` should be whatever the
+/// parts actually work on, so that neither part has to parse or clone the input again.
+pub trait Solver {
+    type Input;
+
+    fn parse(input: &str) -> Self::Input;
+    fn part_one(input: &Self::Input) -> Answer;
+    fn part_two(input: &Self::Input) -> Answer;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl std::fmt::Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Part::One => f.pad("1"),
+            Part::Two => f.pad("2"),
+        }
+    }
+}
+
+/// A registered day with its solver type erased so all days can be stored together.
+pub struct Day {
+    pub day: i32,
+    prepare: fn(&str) -> Box<dyn Parsed>,
+}
+
+impl Day {
+    fn new<S>(day: i32) -> Self
+    where
+        S: Solver + 'static,
+    {
+        Self {
+            day,
+            prepare: prepare::<S>,
+        }
+    }
+
+    /// Parse the input for this day, returning a handle to run each part on.
+    pub fn parse(&self, input: &str) -> Box<dyn Parsed> {
+        (self.prepare)(input)
+    }
+}
+
+/// Parsed input for a day, ready to be solved.
+pub trait Parsed {
+    fn part_one(&self) -> Answer;
+    fn part_two(&self) -> Answer;
+
+    fn solve(&self, part: Part) -> Answer {
+        match part {
+            Part::One => self.part_one(),
+            Part::Two => self.part_two(),
+        }
     }
 }
+
+struct Prepared<S: Solver> {
+    input: S::Input,
+    solver: PhantomData<S>,
+}
+
+impl<S: Solver> Parsed for Prepared<S> {
+    fn part_one(&self) -> Answer {
+        S::part_one(&self.input)
+    }
+
+    fn part_two(&self) -> Answer {
+        S::part_two(&self.input)
+    }
+}
+
+fn prepare<S>(input: &str) -> Box<dyn Parsed>
+where
+    S: Solver + 'static,
+{
+    Box::new(Prepared::<S> {
+        input: S::parse(input),
+        solver: PhantomData,
+    })
+}
+
+/// Find the registered day, if it's implemented.
+pub fn get(day: i32) -> Option<Day> {
+    registry().into_iter().find(|d| d.day == day)
+}
@@ -1,11 +1,93 @@
-#![allow(dead_code)]
-pub(crate) fn this<F>(f: F)
+use std::time::{Duration, Instant};
+
+/// Run `f` once and return the result together with how long it took.
+pub(crate) fn this<F, T>(f: F) -> (T, Duration)
 where
-    F: Fn(),
+    F: FnOnce() -> T,
 {
-    let start = std::time::Instant::now();
-    f();
+    let start = Instant::now();
+    let result = f();
     let duration = start.elapsed();
 
-    println!("TOOK: {:?}", duration);
+    (result, duration)
+}
+
+/// Run `f` `warmup` times without measuring and then `runs` times, collecting statistics over the
+/// measured runs.
+pub(crate) fn repeat<F, T>(warmup: usize, runs: usize, mut f: F) -> Stats
+where
+    F: FnMut() -> T,
+{
+    for _ in 0..warmup {
+        std::hint::black_box(f());
+    }
+
+    let samples = (0..runs)
+        .map(|_| this(|| std::hint::black_box(f())).1)
+        .collect();
+
+    Stats::new(samples)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    fn new(mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+
+        let runs = samples.len();
+        if runs == 0 {
+            return Self {
+                runs,
+                min: Duration::ZERO,
+                median: Duration::ZERO,
+                mean: Duration::ZERO,
+                max: Duration::ZERO,
+            };
+        }
+
+        let median = if runs.is_multiple_of(2) {
+            (samples[runs / 2 - 1] + samples[runs / 2]) / 2
+        } else {
+            samples[runs / 2]
+        };
+
+        Self {
+            runs,
+            min: samples[0],
+            median,
+            mean: samples.iter().sum::<Duration>() / runs as u32,
+            max: samples[runs - 1],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats() {
+        let ms = Duration::from_millis;
+        let stats = Stats::new(vec![ms(4), ms(1), ms(10), ms(3)]);
+
+        assert_eq!(
+            stats,
+            Stats {
+                runs: 4,
+                min: ms(1),
+                median: Duration::from_micros(3500),
+                mean: Duration::from_micros(4500),
+                max: ms(10),
+            }
+        );
+        assert_eq!(Stats::new(vec![ms(2), ms(1), ms(3)]).median, ms(2));
+    }
 }